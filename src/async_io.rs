@@ -0,0 +1,93 @@
+//! Asynchronous file identification, enabled by the `async` feature.
+
+use std::io::{self, SeekFrom};
+use std::path::Path;
+
+use tokio::fs;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeekExt};
+
+use crate::{
+    FileInfo, FileProcessingError, create_directory_info, create_special_info, header_read_len, identify_from_bytes,
+    identify_header, tail_read_len, unresolved_link_info, with_metadata,
+};
+
+/// Identify a single file or directory without blocking the async executor.
+/// Only the file system access is asynchronous; detection is the same as for
+/// `identify_multiple`, reading at most `header_read_len()` bytes of the file.
+pub async fn identify_file_from_path_async(path: impl AsRef<Path>) -> Result<FileInfo, FileProcessingError> {
+    let path = path.as_ref();
+    let metadata = match fs::metadata(path).await {
+        Ok(metadata) => metadata,
+        Err(error) => {
+            let link = fs::symlink_metadata(path).await.ok();
+            return link
+                .and_then(|link| unresolved_link_info(path, &link))
+                .ok_or(error.into());
+        }
+    };
+
+    if metadata.is_dir() {
+        return Ok(with_metadata(create_directory_info(path), &metadata));
+    }
+    if let Some(info) = create_special_info(path, metadata.file_type()) {
+        return Ok(with_metadata(info, &metadata));
+    }
+
+    let mut file = fs::File::open(path).await?;
+    let limit = header_read_len();
+    let mut head = Vec::with_capacity(limit);
+    (&mut file).take(limit as u64).read_to_end(&mut head).await?;
+
+    // Trailer signatures need the end of the file if the head didn't reach it
+    let tail_len = tail_read_len() as u64;
+    let mut tail = Vec::new();
+    if head.len() == limit && tail_len > 0 {
+        file.seek(SeekFrom::End(-(tail_len.min(metadata.len()) as i64))).await?;
+        file.take(tail_len).read_to_end(&mut tail).await?;
+    }
+    let tail = if tail.is_empty() { &head } else { &tail };
+
+    Ok(with_metadata(identify_header(path, &head, tail, metadata.len()), &metadata))
+}
+
+/// Identify the file type from an async reader, such as an upload stream,
+/// without buffering the whole input. Like `identify_from_reader`, consumes at
+/// most `header_read_len()` bytes from the reader, stopping early at EOF, so
+/// trailer signatures are only matched for inputs that end within them.
+/// Since the total length is unknown, the returned FileInfo has `size: None`.
+pub async fn identify_from_async_reader<R: AsyncRead + Unpin>(reader: &mut R) -> io::Result<Option<FileInfo>> {
+    let limit = header_read_len();
+    let mut header = Vec::with_capacity(limit);
+    reader.take(limit as u64).read_to_end(&mut header).await?;
+    Ok(identify_from_bytes(&header).map(|info| FileInfo { size: None, ..info }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::identify_multiple;
+
+    #[tokio::test]
+    async fn async_matches_sync() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("image.png");
+        std::fs::write(&path, b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR").unwrap();
+
+        let info = identify_file_from_path_async(&path).await.unwrap();
+        assert_eq!(info.mime_type.as_deref(), Some("image/png"));
+        assert_eq!(info, identify_multiple(&[&path]).unwrap().remove(0));
+    }
+
+    #[tokio::test]
+    async fn async_reader_reads_only_the_header() {
+        let mut bytes = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR".to_vec();
+        bytes.resize(header_read_len() * 4, 0);
+        let mut cursor = std::io::Cursor::new(bytes);
+
+        let info = identify_from_async_reader(&mut cursor).await.unwrap().unwrap();
+        assert_eq!(info.mime_type.as_deref(), Some("image/png"));
+        assert_eq!(info.size, None);
+        assert_eq!(cursor.position(), header_read_len() as u64);
+    }
+}
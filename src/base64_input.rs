@@ -0,0 +1,46 @@
+//! Identification of base64-encoded data, enabled by the `base64` feature.
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+
+use crate::{FileInfo, identify_from_bytes};
+
+/// Error decoding the input of `identify_from_base64`.
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid base64 data: {0}")]
+pub struct Base64Error(#[from] base64::DecodeError);
+
+/// Decode standard, padded base64 and identify the decoded bytes as
+/// `identify_from_bytes` does. A `data:` URL prefix such as
+/// `data:image/png;base64,` is skipped, as is surrounding whitespace.
+/// Fails if the data isn't valid base64; returns Ok(None) if it decodes but
+/// isn't recognized.
+pub fn identify_from_base64(data: &str) -> Result<Option<FileInfo>, Base64Error> {
+    let data = data.trim();
+    let data = match data.strip_prefix("data:").and_then(|url| url.split_once(";base64,")) {
+        Some((_, payload)) => payload,
+        None => data,
+    };
+
+    Ok(identify_from_bytes(&STANDARD.decode(data)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_base64_png() {
+        let info = identify_from_base64("iVBORw0KGgo=").unwrap().unwrap();
+        assert_eq!(info.mime_type.as_deref(), Some("image/png"));
+
+        let info = identify_from_base64(" data:image/png;base64,iVBORw0KGgo=\n").unwrap().unwrap();
+        assert_eq!(info.mime_type.as_deref(), Some("image/png"));
+    }
+
+    #[test]
+    fn invalid_base64_is_an_error() {
+        let error = identify_from_base64("not base64!").unwrap_err();
+        assert!(error.to_string().starts_with("Invalid base64 data"));
+    }
+}
@@ -0,0 +1,161 @@
+/// Coarse grouping of detected file types, suitable for file browsers and reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Category {
+    Image,
+    Audio,
+    Video,
+    Archive,
+    Document,
+    Executable,
+    Text,
+    Directory,
+    Other,
+}
+
+/// Archive and compression MIME types under `application/`.
+const ARCHIVE_MIMES: &[&str] = &[
+    "application/zip",
+    "application/gzip",
+    "application/x-tar",
+    "application/x-7z-compressed",
+    "application/vnd.rar",
+    "application/x-bzip2",
+    "application/x-xz",
+    "application/zstd",
+    "application/zlib",
+    "application/x-compress",
+    "application/x-cpio",
+    "application/x-lz4",
+    "application/x-zoo",
+    "application/x-unix-archive",
+    "application/x-rpm",
+    "application/vnd.debian.binary-package",
+    "application/vnd.ms-cab-compressed",
+    "application/java-archive",
+];
+
+/// Document MIME types under `application/`, in addition to the Office and
+/// OpenDocument families matched by prefix.
+const DOCUMENT_MIMES: &[&str] = &[
+    "application/pdf",
+    "application/postscript",
+    "application/msword",
+    "application/rtf",
+    "application/epub+zip",
+];
+
+/// Executable MIME types under `application/`.
+const EXECUTABLE_MIMES: &[&str] = &[
+    "application/vnd.microsoft.portable-executable",
+    "application/x-executable",
+    "application/x-sharedlib",
+    "application/x-mach-binary",
+    "application/java-vm",
+    "application/vnd.android.dex",
+    "application/wasm",
+];
+
+/// Structured text MIME types under `application/`.
+const TEXT_MIMES: &[&str] = &[
+    "application/json",
+    "application/xml",
+    "application/yaml",
+    "application/toml",
+    "application/sql",
+    "application/typescript",
+    "application/xhtml+xml",
+    "application/rss+xml",
+    "application/atom+xml",
+];
+
+/// Description keywords checked when no MIME type decides the category.
+/// Earlier entries take priority.
+const DESCRIPTION_KEYWORDS: &[(&str, Category)] = &[
+    ("executable", Category::Executable),
+    ("shared object", Category::Executable),
+    ("video", Category::Video),
+    ("movie", Category::Video),
+    ("audio", Category::Audio),
+    ("image", Category::Image),
+    ("bitmap", Category::Image),
+    ("icon", Category::Image),
+    ("archive", Category::Archive),
+    ("compressed", Category::Archive),
+    ("package", Category::Archive),
+    ("document", Category::Document),
+    ("script", Category::Text),
+    ("text", Category::Text),
+];
+
+/// Map a MIME type and/or description to a category.
+/// The MIME type is preferred; the description is used for custom magic
+/// entries that have no MIME type or one that doesn't determine the category.
+pub(crate) fn categorize(mime: Option<&str>, description: &str) -> Category {
+    if let Some(category) = mime.and_then(categorize_mime) {
+        return category;
+    }
+
+    let description = description.to_lowercase();
+    DESCRIPTION_KEYWORDS
+        .iter()
+        .find(|(keyword, _)| description.contains(keyword))
+        .map_or(Category::Other, |(_, category)| *category)
+}
+
+fn categorize_mime(mime: &str) -> Option<Category> {
+    let (top, sub) = mime.split_once('/')?;
+    match top {
+        "image" => Some(Category::Image),
+        "audio" => Some(Category::Audio),
+        "video" => Some(Category::Video),
+        "font" => Some(Category::Other),
+        "text" if sub == "rtf" => Some(Category::Document),
+        "text" => Some(Category::Text),
+        "application" if ARCHIVE_MIMES.contains(&mime) => Some(Category::Archive),
+        "application" if EXECUTABLE_MIMES.contains(&mime) => Some(Category::Executable),
+        "application" if TEXT_MIMES.contains(&mime) => Some(Category::Text),
+        "application"
+            if DOCUMENT_MIMES.contains(&mime)
+                || sub.starts_with("vnd.openxmlformats-officedocument.")
+                || sub.starts_with("vnd.oasis.opendocument.")
+                || sub.starts_with("vnd.ms-") =>
+        {
+            Some(Category::Document)
+        }
+        "application" if sub == "ogg" => Some(Category::Audio),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{identify_from_bytes, identify_many_bytes};
+
+    #[test]
+    fn png_is_image() {
+        let info = identify_from_bytes(b"\x89PNG\r\n\x1a\n").unwrap();
+        assert_eq!(info.category(), Category::Image);
+    }
+
+    #[test]
+    fn zip_is_archive() {
+        let info = identify_from_bytes(b"PK\x03\x04\x14\x00\x00\x00").unwrap();
+        assert_eq!(info.category(), Category::Archive);
+    }
+
+    #[test]
+    fn unknown_blob_is_other() {
+        let info = &identify_many_bytes([&[0x00, 0xDE, 0xAD, 0x00, 0xBE, 0xEF][..]])[0];
+        assert!(info.is_unknown());
+        assert_eq!(info.category(), Category::Other);
+    }
+
+    #[test]
+    fn description_decides_without_mime() {
+        assert_eq!(categorize(None, "Custom movie container"), Category::Video);
+        assert_eq!(categorize(None, "Something else"), Category::Other);
+    }
+}
@@ -0,0 +1,127 @@
+//! Identification of compressed streams, independent of their contents.
+
+use crate::containers::zip_sizes;
+
+/// Compression format of a stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Compression {
+    Gzip,
+    Bzip2,
+    Xz,
+    Zstd,
+}
+
+const SIGNATURES: &[(&[u8], Compression)] = &[
+    (&[0x1F, 0x8B], Compression::Gzip),
+    (b"BZh", Compression::Bzip2),
+    (&[0xFD, 0x37, 0x7A, 0x58, 0x5A, 0x00], Compression::Xz),
+    (&[0x28, 0xB5, 0x2F, 0xFD], Compression::Zstd),
+];
+
+/// gzip header flag: an extra field follows the fixed header
+const GZIP_FEXTRA: u8 = 0x04;
+/// gzip header flag: the original file name follows, zero-terminated
+const GZIP_FNAME: u8 = 0x08;
+/// Length of the fixed part of a gzip member header
+const GZIP_HEADER_LEN: usize = 10;
+
+/// Identify the compression format of a stream by its signature, whatever
+/// the compressed content is.
+/// Returns None if the input doesn't start with a known compression signature.
+pub fn compression(bytes: &[u8]) -> Option<Compression> {
+    SIGNATURES
+        .iter()
+        .find(|(magic, _)| bytes.starts_with(magic))
+        .map(|&(_, compression)| compression)
+}
+
+/// The original file name stored in a gzip header, as written by `gzip` when
+/// compressing a named file.
+/// Returns None if the input isn't gzip data, has no stored name, or the
+/// header is truncated.
+pub fn gzip_original_name(bytes: &[u8]) -> Option<String> {
+    if compression(bytes)? != Compression::Gzip {
+        return None;
+    }
+    let flags = *bytes.get(3)?;
+    if flags & GZIP_FNAME == 0 {
+        return None;
+    }
+
+    let mut pos = GZIP_HEADER_LEN;
+    if flags & GZIP_FEXTRA != 0 {
+        let extra_len = u16::from_le_bytes(bytes.get(pos..pos + 2)?.try_into().ok()?);
+        pos += 2 + usize::from(extra_len);
+    }
+
+    let name = bytes.get(pos..)?;
+    let end = name.iter().position(|&byte| byte == 0)?;
+    // The name is stored as ISO 8859-1, whose code points map directly to chars
+    Some(name[..end].iter().map(|&byte| char::from(byte)).collect())
+}
+
+/// Ratio of uncompressed to compressed size recorded in a ZIP archive or gzip
+/// stream, for flagging likely decompression bombs before decompressing.
+/// For ZIP, the sizes of all entries in the central directory (or, without one,
+/// the local headers) are summed. For gzip, the size stored in the trailer is
+/// compared to the length of `bytes`, so the whole stream must be passed; the
+/// stored size wraps at 4 GiB.
+/// Returns None for other formats, or if no compressed data is recorded.
+pub fn compression_ratio(bytes: &[u8]) -> Option<f64> {
+    let (compressed, uncompressed) = if bytes.starts_with(b"PK\x03\x04") {
+        zip_sizes(bytes)?
+    } else if compression(bytes)? == Compression::Gzip {
+        let trailer = bytes.get(bytes.len().checked_sub(4)?..)?;
+        (bytes.len() as u64, u64::from(u32::from_le_bytes(trailer.try_into().ok()?)))
+    } else {
+        return None;
+    };
+
+    (compressed > 0).then(|| uncompressed as f64 / compressed as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_signature() {
+        assert_eq!(compression(b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x00\x03"), Some(Compression::Gzip));
+        assert_eq!(compression(b"BZh91AY&SY"), Some(Compression::Bzip2));
+        assert_eq!(compression(b"\xfd7zXZ\x00\x00\x04"), Some(Compression::Xz));
+        assert_eq!(compression(b"\x28\xb5\x2f\xfd\x24\x00"), Some(Compression::Zstd));
+        assert_eq!(compression(b"PK\x03\x04"), None);
+    }
+
+    #[test]
+    fn gzip_stored_name() {
+        let named = b"\x1f\x8b\x08\x08\x00\x00\x00\x00\x00\x03notes.txt\x00\x4b\x4c";
+        assert_eq!(gzip_original_name(named).as_deref(), Some("notes.txt"));
+        assert_eq!(gzip_original_name(b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x00\x03"), None);
+    }
+
+    #[test]
+    fn zip_declaring_large_uncompressed_size() {
+        // Local header: deflated entry "bomb" of 100 bytes declaring 100 MB
+        let mut zip = b"PK\x03\x04\x14\x00\x00\x00\x08\x00\x00\x00\x00\x00\x00\x00\x00\x00".to_vec();
+        zip.extend_from_slice(&100u32.to_le_bytes());
+        zip.extend_from_slice(&100_000_000u32.to_le_bytes());
+        zip.extend_from_slice(&[4, 0, 0, 0]);
+        zip.extend_from_slice(b"bomb");
+        zip.extend_from_slice(&[0; 100]);
+
+        assert_eq!(compression_ratio(&zip), Some(1_000_000.0));
+    }
+
+    #[test]
+    fn gzip_ratio_uses_trailer_size() {
+        let mut gzip = b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x00\x03".to_vec();
+        gzip.extend_from_slice(&[0; 6]);
+        gzip.extend_from_slice(&[0, 0, 0, 0]);
+        gzip.extend_from_slice(&(1u32 << 20).to_le_bytes());
+
+        assert_eq!(compression_ratio(&gzip), Some(f64::from(1u32 << 20) / 24.0));
+        assert_eq!(compression_ratio(b"BZh91AY&SY"), None);
+    }
+}
@@ -0,0 +1,585 @@
+//! Refinement of generic container formats into the specific formats built on them.
+
+const LOCAL_HEADER_SIG: &[u8] = b"PK\x03\x04";
+const CENTRAL_HEADER_SIG: &[u8] = b"PK\x01\x02";
+
+const ZIP_EMPTY_SIG: &[u8] = b"PK\x05\x06";
+
+/// Upper bound on the number of ZIP entry names collected from one buffer.
+const MAX_ZIP_NAMES: usize = 1024;
+
+/// Upper bound on the number of entries returned by `list_archive_entries`.
+pub const MAX_ARCHIVE_ENTRIES: usize = 4096;
+
+const CFB_SIG: &[u8] = &[0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+const CFB_DIR_ENTRY_LEN: usize = 128;
+/// Sector numbers from here up are markers such as end-of-chain, not sectors
+const CFB_MAX_REGULAR_SECTOR: u32 = 0xFFFF_FFFA;
+/// FAT sector numbers listed in the header; larger files continue the list
+/// in DIFAT sectors, which aren't followed
+const CFB_HEADER_DIFAT: std::ops::Range<usize> = 76..512;
+/// Upper bound on the directory sectors read, which also stops cyclic chains
+const CFB_MAX_DIR_SECTORS: usize = 64;
+
+/// Streams that identify the application that wrote a compound file.
+const CFB_KINDS: &[(&str, &str, &str)] = &[
+    ("WordDocument", "Microsoft Word 97-2003 document (DOC)", "application/msword"),
+    ("Workbook", "Microsoft Excel 97-2003 spreadsheet (XLS)", "application/vnd.ms-excel"),
+    // Excel 5.0/95
+    ("Book", "Microsoft Excel 97-2003 spreadsheet (XLS)", "application/vnd.ms-excel"),
+    ("PowerPoint Document", "Microsoft PowerPoint 97-2003 presentation (PPT)", "application/vnd.ms-powerpoint"),
+];
+
+const TAR_BLOCK_LEN: usize = 512;
+const TAR_MAGIC: &[u8] = b"ustar";
+/// GNU tar entry whose data is the long name of the following entry
+const TAR_GNU_LONG_NAME: u8 = b'L';
+/// POSIX pax extended header entries, which describe other entries
+const TAR_PAX_HEADERS: &[u8] = b"xg";
+
+/// Identify the specific format of a ZIP-based file, such as DOCX, JAR or EPUB,
+/// by inspecting its entry names and the `mimetype` entry.
+/// Returns a description of the specific format, or None if the input is not
+/// a ZIP archive or is a plain one.
+pub fn refine_zip(bytes: &[u8]) -> Option<String> {
+    zip_kind(bytes).map(|(description, _)| description.to_string())
+}
+
+/// Description and MIME type of the specific format of a ZIP-based file.
+pub(crate) fn zip_kind(bytes: &[u8]) -> Option<(&'static str, Option<&'static str>)> {
+    if !bytes.starts_with(LOCAL_HEADER_SIG) {
+        return None;
+    }
+
+    // OpenDocument and EPUB store their MIME type uncompressed as the first entry
+    if let Some(mimetype) = stored_mimetype(bytes) {
+        return Some(match mimetype {
+            b"application/epub+zip" => ("EPUB e-book", Some("application/epub+zip")),
+            b"application/vnd.oasis.opendocument.text" => {
+                ("OpenDocument Text (ODT)", Some("application/vnd.oasis.opendocument.text"))
+            }
+            b"application/vnd.oasis.opendocument.spreadsheet" => {
+                ("OpenDocument Spreadsheet (ODS)", Some("application/vnd.oasis.opendocument.spreadsheet"))
+            }
+            b"application/vnd.oasis.opendocument.presentation" => {
+                ("OpenDocument Presentation (ODP)", Some("application/vnd.oasis.opendocument.presentation"))
+            }
+            _ => ("OpenDocument or other mimetype-tagged ZIP container", None),
+        });
+    }
+
+    let names = zip_entry_names(bytes, MAX_ZIP_NAMES);
+    let has = |name: &str| names.iter().any(|entry| entry == name);
+    let has_prefix = |prefix: &str| names.iter().any(|entry| entry.starts_with(prefix));
+
+    if has("[Content_Types].xml") {
+        return Some(if has_prefix("word/") {
+            (
+                "Microsoft Word 2007+ document (DOCX)",
+                Some("application/vnd.openxmlformats-officedocument.wordprocessingml.document"),
+            )
+        } else if has_prefix("xl/") {
+            (
+                "Microsoft Excel 2007+ spreadsheet (XLSX)",
+                Some("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"),
+            )
+        } else if has_prefix("ppt/") {
+            (
+                "Microsoft PowerPoint 2007+ presentation (PPTX)",
+                Some("application/vnd.openxmlformats-officedocument.presentationml.presentation"),
+            )
+        } else {
+            ("Office Open XML document", None)
+        });
+    }
+    if has("AndroidManifest.xml") {
+        return Some(("Android application package (APK)", Some("application/vnd.android.package-archive")));
+    }
+    if has("META-INF/MANIFEST.MF") {
+        return Some(("Java archive (JAR)", Some("application/java-archive")));
+    }
+
+    None
+}
+
+/// Contents of a stored (uncompressed) `mimetype` entry at the start of the archive.
+fn stored_mimetype(bytes: &[u8]) -> Option<&[u8]> {
+    let method = read_u16_le(bytes, 8)?;
+    let compressed_size = read_u32_le(bytes, 18)? as usize;
+    let name_len = usize::from(read_u16_le(bytes, 26)?);
+    let extra_len = usize::from(read_u16_le(bytes, 28)?);
+
+    if method != 0 || bytes.get(30..30 + name_len)? != b"mimetype" {
+        return None;
+    }
+
+    let start = 30 + name_len + extra_len;
+    bytes.get(start..start.checked_add(compressed_size)?)
+}
+
+/// List the member paths of a ZIP or TAR archive from its directory or headers,
+/// without extracting anything. At most `MAX_ARCHIVE_ENTRIES` paths are
+/// returned, however many the archive claims to hold. The buffer may be
+/// truncated; only the entries it contains are listed.
+/// Returns None if the input is neither a ZIP nor a ustar/GNU TAR archive.
+pub fn list_archive_entries(bytes: &[u8]) -> Option<Vec<String>> {
+    if bytes.starts_with(LOCAL_HEADER_SIG) || bytes.starts_with(ZIP_EMPTY_SIG) {
+        Some(zip_entry_names(bytes, MAX_ARCHIVE_ENTRIES))
+    } else if bytes.get(257..257 + TAR_MAGIC.len()) == Some(TAR_MAGIC) {
+        Some(tar_entry_names(bytes, MAX_ARCHIVE_ENTRIES))
+    } else {
+        None
+    }
+}
+
+/// Collect up to `limit` entry names from a TAR buffer by walking its headers
+/// until the end-of-archive block or the end of the buffer.
+fn tar_entry_names(bytes: &[u8], limit: usize) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut long_name: Option<String> = None;
+    let mut pos = 0;
+
+    while names.len() < limit {
+        let Some(header) = slice_at(bytes, pos, TAR_BLOCK_LEN) else { break };
+        if header.iter().all(|&byte| byte == 0) {
+            break;
+        }
+        let Some(size) = parse_octal(&header[124..136]) else { break };
+        let Some(data_start) = pos.checked_add(TAR_BLOCK_LEN) else { break };
+        let typeflag = header[156];
+
+        if typeflag == TAR_GNU_LONG_NAME {
+            long_name = bytes
+                .get(data_start..data_start.saturating_add(size))
+                .map(|name| String::from_utf8_lossy(until_nul(name)).into_owned());
+        } else if !TAR_PAX_HEADERS.contains(&typeflag) {
+            names.push(long_name.take().unwrap_or_else(|| tar_header_name(header)));
+        }
+
+        match size.div_ceil(TAR_BLOCK_LEN).checked_mul(TAR_BLOCK_LEN).and_then(|len| data_start.checked_add(len)) {
+            Some(next) => pos = next,
+            None => break,
+        }
+    }
+
+    names
+}
+
+/// The path in a TAR header, joined with the ustar prefix field if present.
+fn tar_header_name(header: &[u8]) -> String {
+    let name = String::from_utf8_lossy(until_nul(&header[..100]));
+    let prefix = until_nul(&header[345..500]);
+    if header[257..263] == *b"ustar\0" && !prefix.is_empty() {
+        format!("{}/{name}", String::from_utf8_lossy(prefix))
+    } else {
+        name.into_owned()
+    }
+}
+
+/// A TAR numeric field: octal digits, padded with spaces or NULs.
+fn parse_octal(field: &[u8]) -> Option<usize> {
+    let digits = until_nul(field).trim_ascii();
+    if digits.is_empty() {
+        return Some(0);
+    }
+    usize::from_str_radix(std::str::from_utf8(digits).ok()?, 8).ok()
+}
+
+/// The bytes before the first NUL.
+fn until_nul(bytes: &[u8]) -> &[u8] {
+    bytes.split(|&byte| byte == 0).next().unwrap_or(bytes)
+}
+
+/// Collect up to `limit` entry names from a ZIP buffer, which may be truncated.
+/// Local file headers are followed from the start of the buffer for as long as
+/// their sizes are known, and any central directory records present in the
+/// buffer are read as well. Duplicates are removed, keeping first-seen order.
+pub(crate) fn zip_entry_names(bytes: &[u8], limit: usize) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    let mut push = |name: &[u8]| {
+        let name = String::from_utf8_lossy(name).into_owned();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    };
+
+    // Walk local file headers
+    let mut pos = 0;
+    let mut count = 0;
+    while count < limit && slice_at(bytes, pos, 4) == Some(LOCAL_HEADER_SIG) {
+        let Some(header) = LocalHeader::parse(bytes, pos) else { break };
+        let Some(name) = bytes.get(header.name_range.clone()) else { break };
+        push(name);
+        count += 1;
+
+        // With a data descriptor the sizes are stored after the data, so the
+        // next header can't be located
+        if header.has_data_descriptor && header.compressed_size == 0 {
+            break;
+        }
+        match header.data_end() {
+            Some(next) => pos = next,
+            None => break,
+        }
+    }
+
+    // Pick up central directory records if the end of the archive is present
+    let mut search_from = 0;
+    while count < limit {
+        let Some(found) = find(&bytes[search_from..], CENTRAL_HEADER_SIG) else { break };
+        let pos = search_from + found;
+        search_from = pos + CENTRAL_HEADER_SIG.len();
+
+        let Some(name_len) = pos.checked_add(28).and_then(|offset| read_u16_le(bytes, offset)) else { break };
+        let Some(name) = pos.checked_add(46).and_then(|start| slice_at(bytes, start, usize::from(name_len))) else { break };
+        push(name);
+        count += 1;
+    }
+
+    names
+}
+
+/// Total compressed and uncompressed sizes of the entries of a ZIP buffer, from
+/// its central directory if present and its local file headers otherwise.
+/// Entries whose sizes are deferred to a data descriptor count as zero.
+/// Returns None if no entries are found.
+pub(crate) fn zip_sizes(bytes: &[u8]) -> Option<(u64, u64)> {
+    let mut compressed = 0;
+    let mut uncompressed = 0;
+    let mut count = 0;
+
+    let mut search_from = 0;
+    while count < MAX_ARCHIVE_ENTRIES {
+        let Some(found) = find(&bytes[search_from..], CENTRAL_HEADER_SIG) else { break };
+        let pos = search_from + found;
+        search_from = pos + CENTRAL_HEADER_SIG.len();
+
+        let (Some(entry_compressed), Some(entry_uncompressed)) =
+            (pos.checked_add(20).and_then(|offset| read_u32_le(bytes, offset)), pos.checked_add(24).and_then(|offset| read_u32_le(bytes, offset)))
+        else {
+            break;
+        };
+        compressed += u64::from(entry_compressed);
+        uncompressed += u64::from(entry_uncompressed);
+        count += 1;
+    }
+
+    if count == 0 {
+        let mut pos = 0;
+        while count < MAX_ARCHIVE_ENTRIES && slice_at(bytes, pos, 4) == Some(LOCAL_HEADER_SIG) {
+            let (Some(header), Some(entry_uncompressed)) =
+                (LocalHeader::parse(bytes, pos), pos.checked_add(22).and_then(|offset| read_u32_le(bytes, offset)))
+            else {
+                break;
+            };
+            compressed += u64::from(header.compressed_size);
+            uncompressed += u64::from(entry_uncompressed);
+            count += 1;
+
+            match header.data_end() {
+                Some(next) => pos = next,
+                None => break,
+            }
+        }
+    }
+
+    (count > 0).then_some((compressed, uncompressed))
+}
+
+/// The fields of a ZIP local file header needed to walk an archive.
+struct LocalHeader {
+    has_data_descriptor: bool,
+    compressed_size: u32,
+    name_range: std::ops::Range<usize>,
+    extra_len: usize,
+}
+
+impl LocalHeader {
+    /// Parse the local file header starting at `pos`.
+    fn parse(bytes: &[u8], pos: usize) -> Option<Self> {
+        let flags = read_u16_le(bytes, pos.checked_add(6)?)?;
+        let name_len = usize::from(read_u16_le(bytes, pos.checked_add(26)?)?);
+        let name_start = pos.checked_add(30)?;
+
+        Some(Self {
+            has_data_descriptor: flags & 0x0008 != 0,
+            compressed_size: read_u32_le(bytes, pos.checked_add(18)?)?,
+            name_range: name_start..name_start.checked_add(name_len)?,
+            extra_len: usize::from(read_u16_le(bytes, pos.checked_add(28)?)?),
+        })
+    }
+
+    /// Offset just past this entry's data, where the next header begins.
+    fn data_end(&self) -> Option<usize> {
+        self.name_range.end.checked_add(self.extra_len)?.checked_add(self.compressed_size as usize)
+    }
+}
+
+/// Identify the application behind a Microsoft Compound File Binary (OLE2)
+/// file, such as a legacy Word, Excel or PowerPoint file, by the names of the
+/// streams in its directory.
+/// Returns "Compound File Binary" when the directory isn't in the buffer or
+/// names no known stream, and None if the input is not a compound file.
+pub fn cfb_subtype(bytes: &[u8]) -> Option<String> {
+    if !bytes.starts_with(CFB_SIG) {
+        return None;
+    }
+    Some(cfb_kind(bytes).map_or("Compound File Binary", |(description, _)| description).to_string())
+}
+
+/// Description and MIME type of the specific format of a compound file.
+pub(crate) fn cfb_kind(bytes: &[u8]) -> Option<(&'static str, Option<&'static str>)> {
+    if !bytes.starts_with(CFB_SIG) {
+        return None;
+    }
+
+    let names = cfb_entry_names(bytes);
+    CFB_KINDS
+        .iter()
+        .find(|(stream, _, _)| names.iter().any(|name| name == stream))
+        .map(|(_, description, mime)| (*description, Some(*mime)))
+}
+
+/// Names of the storages and streams in a compound file's directory, following
+/// the directory's sector chain as far as the buffer and header allow.
+fn cfb_entry_names(bytes: &[u8]) -> Vec<String> {
+    let mut names = Vec::new();
+    // 512-byte sectors in version 3 files, 4096-byte ones in version 4
+    let Some(sector_len) = read_u16_le(bytes, 30).filter(|shift| matches!(shift, 9 | 12)).map(|shift| 1usize << shift) else {
+        return names;
+    };
+
+    let mut sector = read_u32_le(bytes, 48);
+    for _ in 0..CFB_MAX_DIR_SECTORS {
+        let Some(start) = sector.and_then(|sector| cfb_sector_start(sector, sector_len)) else {
+            break;
+        };
+        let Some(directory) = bytes.get(start..) else {
+            break;
+        };
+
+        let directory = &directory[..directory.len().min(sector_len)];
+        names.extend(directory.chunks_exact(CFB_DIR_ENTRY_LEN).filter_map(cfb_entry_name));
+        sector = sector.and_then(|sector| cfb_next_sector(bytes, sector, sector_len));
+    }
+
+    names
+}
+
+/// Offset of a sector, which follows the header sector. None for markers.
+fn cfb_sector_start(sector: u32, sector_len: usize) -> Option<usize> {
+    if sector >= CFB_MAX_REGULAR_SECTOR {
+        return None;
+    }
+    (sector as usize).checked_add(1)?.checked_mul(sector_len)
+}
+
+/// The sector after `sector` in its chain, from the file allocation table.
+fn cfb_next_sector(bytes: &[u8], sector: u32, sector_len: usize) -> Option<u32> {
+    let per_fat_sector = sector_len / 4;
+    let difat_offset = CFB_HEADER_DIFAT.start + sector as usize / per_fat_sector * 4;
+    if difat_offset >= CFB_HEADER_DIFAT.end {
+        return None;
+    }
+
+    let fat_start = cfb_sector_start(read_u32_le(bytes, difat_offset)?, sector_len)?;
+    read_u32_le(bytes, fat_start.checked_add(sector as usize % per_fat_sector * 4)?)
+}
+
+/// The name of a used directory entry, stored as UTF-16LE with its length in
+/// bytes, including the terminating NUL.
+fn cfb_entry_name(entry: &[u8]) -> Option<String> {
+    // Object type 0 marks an unused entry
+    if entry[66] == 0 {
+        return None;
+    }
+    let name_len = usize::from(read_u16_le(entry, 64)?).min(64);
+    let units: Vec<u16> = entry[..name_len.saturating_sub(2)]
+        .chunks_exact(2)
+        .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+        .collect();
+    Some(String::from_utf16_lossy(&units))
+}
+
+/// Position of the first occurrence of `needle` in `haystack`.
+pub(crate) fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
+
+/// The `len` bytes at `offset`, or None if they run past the buffer or the
+/// end offset overflows.
+fn slice_at(bytes: &[u8], offset: usize, len: usize) -> Option<&[u8]> {
+    bytes.get(offset..offset.checked_add(len)?)
+}
+
+fn read_u16_le(bytes: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(slice_at(bytes, offset, 2)?.try_into().ok()?))
+}
+
+fn read_u32_le(bytes: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(slice_at(bytes, offset, 4)?.try_into().ok()?))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    use crate::identify_from_bytes;
+
+    /// A ZIP archive with the given stored entries and a central directory.
+    pub(crate) fn zip(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut archive = Vec::new();
+        let mut central = Vec::new();
+
+        for (name, data) in entries {
+            let offset = archive.len() as u32;
+            // CRC-32 (left zero), compressed and uncompressed sizes, name and extra field lengths
+            let mut sizes = vec![0; 4];
+            sizes.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            sizes.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            sizes.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            sizes.extend_from_slice(&[0, 0]);
+
+            // Version needed, flags, method (stored), time and date
+            archive.extend_from_slice(LOCAL_HEADER_SIG);
+            archive.extend_from_slice(&[20, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+            archive.extend_from_slice(&sizes);
+            archive.extend_from_slice(name.as_bytes());
+            archive.extend_from_slice(data);
+
+            // Version made by, then as above; after the sizes, comment length,
+            // disk number and attributes
+            central.extend_from_slice(CENTRAL_HEADER_SIG);
+            central.extend_from_slice(&[20, 0, 20, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+            central.extend_from_slice(&sizes);
+            central.extend_from_slice(&[0; 10]);
+            central.extend_from_slice(&offset.to_le_bytes());
+            central.extend_from_slice(name.as_bytes());
+        }
+
+        let central_offset = archive.len() as u32;
+        let count = (entries.len() as u16).to_le_bytes();
+        archive.extend_from_slice(&central);
+        archive.extend_from_slice(ZIP_EMPTY_SIG);
+        archive.extend_from_slice(&[0, 0, 0, 0, count[0], count[1], count[0], count[1]]);
+        archive.extend_from_slice(&(central.len() as u32).to_le_bytes());
+        archive.extend_from_slice(&central_offset.to_le_bytes());
+        archive.extend_from_slice(&[0, 0]);
+        archive
+    }
+
+    /// A ustar archive with the given regular file entries.
+    fn tar(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut archive = Vec::new();
+        for (name, data) in entries {
+            let mut header = [0; TAR_BLOCK_LEN];
+            header[..name.len()].copy_from_slice(name.as_bytes());
+            header[124..135].copy_from_slice(format!("{:011o}", data.len()).as_bytes());
+            header[156] = b'0';
+            header[257..263].copy_from_slice(b"ustar\0");
+            archive.extend_from_slice(&header);
+            archive.extend_from_slice(data);
+            archive.resize(archive.len().next_multiple_of(TAR_BLOCK_LEN), 0);
+        }
+        archive.resize(archive.len() + 2 * TAR_BLOCK_LEN, 0);
+        archive
+    }
+
+    /// A version 3 compound file whose single directory sector lists a root
+    /// storage and the given streams.
+    fn cfb(streams: &[&str]) -> Vec<u8> {
+        let sector_len = 512;
+        let mut file = vec![0; 3 * sector_len];
+        file[..CFB_SIG.len()].copy_from_slice(CFB_SIG);
+        file[30..32].copy_from_slice(&9u16.to_le_bytes());
+        // Directory in sector 0, file allocation table in sector 1
+        file[48..52].copy_from_slice(&0u32.to_le_bytes());
+        file[76..80].copy_from_slice(&1u32.to_le_bytes());
+        file[2 * sector_len..2 * sector_len + 4].copy_from_slice(&0xFFFF_FFFEu32.to_le_bytes());
+
+        let names = std::iter::once(("Root Entry", 5)).chain(streams.iter().map(|&name| (name, 2)));
+        for (index, (name, object_type)) in names.enumerate() {
+            let entry = &mut file[sector_len + index * CFB_DIR_ENTRY_LEN..][..CFB_DIR_ENTRY_LEN];
+            let name: Vec<u8> = name.encode_utf16().chain([0]).flat_map(u16::to_le_bytes).collect();
+            entry[..name.len()].copy_from_slice(&name);
+            entry[64..66].copy_from_slice(&(name.len() as u16).to_le_bytes());
+            entry[66] = object_type;
+        }
+        file
+    }
+
+    #[test]
+    fn docx_is_refined() {
+        let docx = zip(&[("[Content_Types].xml", b"<Types/>"), ("word/document.xml", b"<w:document/>")]);
+        assert_eq!(refine_zip(&docx).as_deref(), Some("Microsoft Word 2007+ document (DOCX)"));
+
+        let info = identify_from_bytes(&docx).unwrap();
+        assert_eq!(info.description, "Microsoft Word 2007+ document (DOCX)");
+        assert_eq!(
+            info.mime_type.as_deref(),
+            Some("application/vnd.openxmlformats-officedocument.wordprocessingml.document")
+        );
+    }
+
+    #[test]
+    fn jar_is_refined() {
+        let jar = zip(&[("META-INF/MANIFEST.MF", b"Manifest-Version: 1.0\n"), ("Main.class", b"\xca\xfe\xba\xbe")]);
+        assert_eq!(refine_zip(&jar).as_deref(), Some("Java archive (JAR)"));
+        assert_eq!(identify_from_bytes(&jar).unwrap().mime_type.as_deref(), Some("application/java-archive"));
+    }
+
+    #[test]
+    fn plain_zip_is_not_refined() {
+        assert_eq!(refine_zip(&zip(&[("notes.txt", b"hello")])), None);
+        assert_eq!(refine_zip(b"not a zip"), None);
+    }
+
+    #[test]
+    fn zip_entries_are_listed() {
+        let archive = zip(&[("a.txt", b"one"), ("dir/b.txt", b"two")]);
+        assert_eq!(list_archive_entries(&archive).unwrap(), ["a.txt", "dir/b.txt"]);
+    }
+
+    #[test]
+    fn tar_entries_are_listed() {
+        let archive = tar(&[("a.txt", b"one"), ("dir/b.txt", &[b'x'; 600])]);
+        assert_eq!(list_archive_entries(&archive).unwrap(), ["a.txt", "dir/b.txt"]);
+    }
+
+    #[test]
+    fn entry_list_is_capped() {
+        let names: Vec<String> = (0..MAX_ARCHIVE_ENTRIES + 10).map(|i| format!("{i}.txt")).collect();
+        let entries: Vec<(&str, &[u8])> = names.iter().map(|name| (name.as_str(), &b""[..])).collect();
+        assert_eq!(list_archive_entries(&zip(&entries)).unwrap().len(), MAX_ARCHIVE_ENTRIES);
+    }
+
+    #[test]
+    fn non_archives_are_not_listed() {
+        assert_eq!(list_archive_entries(b"\x89PNG\r\n\x1a\n"), None);
+    }
+
+    #[test]
+    fn cfb_subtype_from_stream_names() {
+        let doc = cfb(&["WordDocument", "1Table"]);
+        assert_eq!(cfb_subtype(&doc).as_deref(), Some("Microsoft Word 97-2003 document (DOC)"));
+        assert_eq!(identify_from_bytes(&doc).unwrap().mime_type.as_deref(), Some("application/msword"));
+
+        let xls = cfb(&["Workbook"]);
+        assert_eq!(cfb_subtype(&xls).as_deref(), Some("Microsoft Excel 97-2003 spreadsheet (XLS)"));
+    }
+
+    #[test]
+    fn cfb_without_known_streams_is_generic() {
+        assert_eq!(cfb_subtype(&cfb(&["Contents"])).as_deref(), Some("Compound File Binary"));
+        assert_eq!(cfb_subtype(CFB_SIG).as_deref(), Some("Compound File Binary"));
+        assert_eq!(cfb_subtype(b"\x89PNG\r\n\x1a\n"), None);
+    }
+
+    #[test]
+    fn offsets_near_the_address_limit_do_not_overflow() {
+        let bytes = zip(&[("a.txt", b"hi")]);
+        assert_eq!(slice_at(&bytes, usize::MAX, 2), None);
+        assert_eq!(read_u32_le(&bytes, usize::MAX - 1), None);
+        assert!(LocalHeader::parse(&bytes, usize::MAX - 20).is_none());
+    }
+}
@@ -0,0 +1,143 @@
+//! Signature matching against the built-in magic number table, usable without
+//! `std`. The `std` API layers container and script refinement, the `infer`
+//! fallback and text classification on top of this.
+
+use crate::magicnums::{MAGIC_NUMBERS, MagicEntry};
+
+/// Identify `bytes` by the most specific matching entry in the built-in magic
+/// number table. Returns the entry's description and MIME type, or None if no
+/// signature matches. Unlike `identify_from_bytes`, ZIP containers and scripts
+/// are not refined and there is no fallback detection.
+pub fn identify(bytes: &[u8]) -> Option<(&'static str, Option<&'static str>)> {
+    best_magic_match(MAGIC_NUMBERS, bytes, bytes).map(|entry| (entry.description, entry.mime))
+}
+
+/// Find the most specific magic entry matching the input, given its first bytes
+/// `head` and last bytes `tail` (for entries flagged `from_end`): the one with the longest
+/// magic, tie-broken by the largest offset. Among equally specific entries the
+/// first one in table order wins.
+pub(crate) fn best_magic_match<'a, I>(entries: I, head: &[u8], tail: &[u8]) -> Option<&'a MagicEntry>
+where
+    I: IntoIterator<Item = &'a MagicEntry>,
+{
+    let mut best: Option<&MagicEntry> = None;
+
+    for entry in entries {
+        let matches = entry_matches(entry, head, tail);
+        let more_specific = best.is_none_or(|current| {
+            (entry.magic.len(), entry.offset) > (current.magic.len(), current.offset)
+        });
+
+        if matches && more_specific {
+            best = Some(entry);
+        }
+    }
+
+    best
+}
+
+/// Whether `entry` matches an input with first bytes `head` and last bytes `tail`.
+pub(crate) fn entry_matches(entry: &MagicEntry, head: &[u8], tail: &[u8]) -> bool {
+    match_offset(entry, head, tail).is_some()
+}
+
+/// Where `entry`'s magic was found: its offset from the start of the input, or
+/// from the end for entries flagged `from_end`. None if it doesn't match.
+pub(crate) fn match_offset(entry: &MagicEntry, head: &[u8], tail: &[u8]) -> Option<usize> {
+    if let Some((start, end)) = entry.search_window {
+        magic_in_window(entry.magic, head, start, end)
+    } else if entry.from_end {
+        magic_at_end(entry, tail).then_some(entry.offset)
+    } else {
+        magic_at(entry, head).then_some(entry.offset)
+    }
+}
+
+/// Position of the first occurrence of `magic` lying entirely within
+/// `bytes[start..end]`, with the window clamped to the available bytes.
+fn magic_in_window(magic: &[u8], bytes: &[u8], start: usize, end: usize) -> Option<usize> {
+    let window = bytes.get(start..end.min(bytes.len()))?;
+    if magic.is_empty() {
+        return Some(start);
+    }
+    window
+        .windows(magic.len())
+        .position(|candidate| candidate == magic)
+        .map(|position| start + position)
+}
+
+/// Whether `entry`'s magic appears at its offset in `bytes`. Never panics,
+/// however large the offset.
+fn magic_at(entry: &MagicEntry, bytes: &[u8]) -> bool {
+    entry
+        .offset
+        .checked_add(entry.magic.len())
+        .and_then(|end| bytes.get(entry.offset..end))
+        .is_some_and(|window| window == entry.magic)
+}
+
+/// Whether `entry`'s magic starts `offset` bytes before the end of `bytes`.
+fn magic_at_end(entry: &MagicEntry, bytes: &[u8]) -> bool {
+    bytes
+        .len()
+        .checked_sub(entry.offset)
+        .and_then(|start| bytes.get(start..start.checked_add(entry.magic.len())?))
+        .is_some_and(|window| window == entry.magic)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(offset: usize, magic: &'static [u8], description: &'static str) -> MagicEntry {
+        MagicEntry { offset, magic, description, mime: None, from_end: false, search_window: None }
+    }
+
+    #[test]
+    fn longest_magic_wins() {
+        let entries = [entry(0, b"RIFF", "RIFF container"), entry(8, b"WEBPVP8 ", "WebP image")];
+        let bytes = b"RIFF\x24\x00\x00\x00WEBPVP8 ";
+        assert_eq!(best_magic_match(&entries, bytes, bytes).unwrap().description, "WebP image");
+    }
+
+    #[test]
+    fn larger_offset_breaks_ties() {
+        let entries = [entry(0, b"RIFF", "RIFF container"), entry(8, b"WAVE", "WAVE audio")];
+        let bytes = b"RIFF\x24\x00\x00\x00WAVEfmt ";
+        assert_eq!(best_magic_match(&entries, bytes, bytes).unwrap().description, "WAVE audio");
+        assert_eq!(best_magic_match(&entries, &bytes[..8], &bytes[..8]).unwrap().description, "RIFF container");
+    }
+
+    #[test]
+    fn offsets_past_the_input_never_panic() {
+        let bytes = b"RIFF";
+        for offset in [5, 100, usize::MAX - 1, usize::MAX] {
+            let fixed = entry(offset, b"WAVE", "fixed");
+            let from_end = MagicEntry { from_end: true, ..entry(offset, b"WAVE", "from end") };
+            let windowed = MagicEntry { search_window: Some((offset, usize::MAX)), ..entry(0, b"WAVE", "window") };
+            for candidate in [fixed, from_end, windowed] {
+                assert!(!entry_matches(&candidate, bytes, bytes));
+            }
+        }
+    }
+
+    // Also run by `cargo test --no-default-features`, showing the core path
+    // builds and works without `std`
+    #[test]
+    fn identify_without_std() {
+        assert_eq!(identify(b"\x89PNG\r\n\x1a\n"), Some(("PNG image data, 8-bit depth", Some("image/png"))));
+        assert_eq!(identify(b"PK\x03\x04"), Some(("Zip archive data (PKZIP)", Some("application/zip"))));
+        assert_eq!(identify(&[0xDE, 0xAD]), None);
+    }
+
+    #[test]
+    fn windowed_magic_found_anywhere_in_window() {
+        let floating = MagicEntry { search_window: Some((4, 16)), ..entry(0, b"MARK", "floating") };
+        let found = |bytes: &[u8]| match_offset(&floating, bytes, bytes);
+
+        assert_eq!(found(b"....xxxxxxMARK.."), Some(10));
+        assert_eq!(found(b"....MARK"), Some(4));
+        assert_eq!(found(b"MARK............"), None);
+        assert_eq!(found(b"..............MARK"), None);
+    }
+}
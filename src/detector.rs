@@ -0,0 +1,124 @@
+//! Composable detection steps, for replacing or extending the default
+//! magic number, `infer` and text fallback order.
+
+use crate::registry::{empty_match, infer_match, text_match};
+use crate::{FileInfo, MagicRegistry};
+
+/// A single detection step. Returns Some(FileInfo) if it recognizes `bytes`.
+pub trait Detector {
+    fn detect(&self, bytes: &[u8]) -> Option<FileInfo>;
+}
+
+/// Matches against a `MagicRegistry`: its custom entries, then the built-in
+/// magic numbers.
+#[derive(Debug, Clone, Default)]
+pub struct MagicDetector {
+    registry: MagicRegistry,
+}
+
+impl MagicDetector {
+    /// A detector for the built-in magic numbers only.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A detector for the custom entries of `registry`, then the built-ins.
+    pub fn with_registry(registry: MagicRegistry) -> Self {
+        Self { registry }
+    }
+}
+
+impl Detector for MagicDetector {
+    fn detect(&self, bytes: &[u8]) -> Option<FileInfo> {
+        self.registry.magic_match(bytes, bytes).map(|(info, _)| info)
+    }
+}
+
+/// The `infer` crate's signature detection.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InferDetector;
+
+impl Detector for InferDetector {
+    fn detect(&self, bytes: &[u8]) -> Option<FileInfo> {
+        infer_match(bytes).map(|(info, _)| info)
+    }
+}
+
+/// Text classification, recognizing plain text in the encodings of `TextKind`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TextDetector;
+
+impl Detector for TextDetector {
+    fn detect(&self, bytes: &[u8]) -> Option<FileInfo> {
+        text_match(bytes).map(|(info, _)| info)
+    }
+}
+
+/// An ordered list of detectors; the first to recognize the input wins.
+/// The default chain is `MagicDetector`, `InferDetector`, then `TextDetector`,
+/// which is what `identify_from_bytes` uses.
+pub struct DetectorChain {
+    detectors: Vec<Box<dyn Detector + Send + Sync>>,
+}
+
+impl Default for DetectorChain {
+    fn default() -> Self {
+        Self::new()
+            .with(MagicDetector::new())
+            .with(InferDetector)
+            .with(TextDetector)
+    }
+}
+
+impl DetectorChain {
+    /// Create a chain with no detectors, which recognizes only empty input.
+    pub fn new() -> Self {
+        Self { detectors: Vec::new() }
+    }
+
+    /// Append a detector, tried after those already in the chain.
+    pub fn with(mut self, detector: impl Detector + Send + Sync + 'static) -> Self {
+        self.detectors.push(Box::new(detector));
+        self
+    }
+
+    /// Run the detectors in order. Empty input is reported as "Empty file"
+    /// without consulting any detector.
+    /// Returns Some(FileInfo) from the first detector that recognizes `bytes`,
+    /// or None if none does.
+    pub fn detect(&self, bytes: &[u8]) -> Option<FileInfo> {
+        if let Some((info, _)) = empty_match(bytes) {
+            return Some(info);
+        }
+        self.detectors.iter().find_map(|detector| detector.detect(bytes))
+    }
+}
+
+impl Detector for DetectorChain {
+    fn detect(&self, bytes: &[u8]) -> Option<FileInfo> {
+        DetectorChain::detect(self, bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The built-in table has no WebAssembly entry; only `infer` knows it.
+    const WASM: &[u8] = b"\0asm\x01\0\0\0";
+
+    #[test]
+    fn chain_without_infer_skips_infer_only_formats() {
+        let chain = DetectorChain::new().with(MagicDetector::new()).with(TextDetector);
+        assert_eq!(chain.detect(WASM), None);
+        let info = crate::identify_from_bytes(WASM).unwrap();
+        assert_eq!(info.mime_type.as_deref(), Some("application/wasm"));
+    }
+
+    #[test]
+    fn empty_chain_recognizes_only_empty_input() {
+        let chain = DetectorChain::new();
+        assert_eq!(chain.detect(b"").unwrap().description, "Empty file");
+        assert_eq!(chain.detect(b"hello"), None);
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::{FileInfo, canonical_path};
+
+/// Differences between two scans of the same tree, from `diff_scans`.
+/// Each list is sorted by path.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScanDiff {
+    /// Entries only in the new scan
+    pub added: Vec<FileInfo>,
+    /// Entries only in the old scan
+    pub removed: Vec<FileInfo>,
+    /// Entries in both scans whose type or size differs, as `(old, new)`
+    pub changed: Vec<(FileInfo, FileInfo)>,
+}
+
+/// Compare two scans entry by entry, matching entries by path.
+/// Paths are compared canonically, so `dir/./a` and `dir/a` are the same entry,
+/// as are paths through linked directories while they still exist. A symbolic
+/// link itself is not resolved, so it stays distinct from its target.
+/// An entry has changed if its description, MIME type, directory flag or size differs.
+pub fn diff_scans(old: &[FileInfo], new: &[FileInfo]) -> ScanDiff {
+    let mut old_by_path: BTreeMap<PathBuf, &FileInfo> = old.iter().map(|info| (canonical(&info.path), info)).collect();
+    let mut diff = ScanDiff::default();
+
+    let new_by_path: BTreeMap<PathBuf, &FileInfo> = new.iter().map(|info| (canonical(&info.path), info)).collect();
+    for (path, new_info) in new_by_path {
+        match old_by_path.remove(&path) {
+            Some(old_info) if is_changed(old_info, new_info) => diff.changed.push((old_info.clone(), new_info.clone())),
+            Some(_) => {}
+            None => diff.added.push(new_info.clone()),
+        }
+    }
+    diff.removed = old_by_path.into_values().cloned().collect();
+
+    diff
+}
+
+fn is_changed(old: &FileInfo, new: &FileInfo) -> bool {
+    old.description != new.description
+        || old.mime_type != new.mime_type
+        || old.is_directory != new.is_directory
+        || old.size != new.size
+}
+
+/// The canonical form of `path` if it exists, or else its lexically
+/// normalized form.
+fn canonical(path: &Path) -> PathBuf {
+    canonical_path(path).unwrap_or_else(|| path.components().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::{fs, slice};
+
+    use crate::identify_file_from_path;
+
+    #[test]
+    fn type_change_is_reported_as_changed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file");
+        fs::write(&path, b"\x89PNG\r\n\x1a\n").unwrap();
+        let old = identify_file_from_path(&path).unwrap();
+        fs::write(&path, b"plain text now\n").unwrap();
+        let new = identify_file_from_path(dir.path().join(".").join("file")).unwrap();
+
+        let diff = diff_scans(slice::from_ref(&old), slice::from_ref(&new));
+        assert_eq!(diff.changed, [(old.clone(), new)]);
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff_scans(slice::from_ref(&old), slice::from_ref(&old)), ScanDiff::default());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlink_is_distinct_from_its_target() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("target.png");
+        let link = dir.path().join("link.png");
+        fs::write(&target, b"\x89PNG\r\n\x1a\n").unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+        let link_info = identify_file_from_path(&link).unwrap();
+        let target_info = identify_file_from_path(&target).unwrap();
+
+        let diff = diff_scans(slice::from_ref(&link_info), slice::from_ref(&target_info));
+        assert_eq!(diff.removed, [link_info]);
+        assert_eq!(diff.added, [target_info]);
+        assert!(diff.changed.is_empty());
+    }
+}
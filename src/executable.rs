@@ -0,0 +1,187 @@
+//! Details of executable and object files beyond their type.
+
+/// Container format of an executable, from `executable_info`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ExecutableFormat {
+    Elf,
+    Pe,
+    MachO,
+}
+
+/// Format, word size and target architecture of an executable, from `executable_info`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExecutableInfo {
+    pub format: ExecutableFormat,
+    /// 32 or 64, or None if the header doesn't say
+    pub bits: Option<u8>,
+    /// Target architecture, e.g. "x86-64" or "AArch64", or None if unrecognized
+    pub architecture: Option<&'static str>,
+}
+
+/// ELF `e_machine` values
+const ELF_MACHINES: &[(u16, &str)] = &[
+    (2, "SPARC"),
+    (3, "x86"),
+    (8, "MIPS"),
+    (20, "PowerPC"),
+    (21, "PowerPC64"),
+    (22, "S/390"),
+    (40, "ARM"),
+    (43, "SPARC V9"),
+    (50, "IA-64"),
+    (62, "x86-64"),
+    (183, "AArch64"),
+    (243, "RISC-V"),
+    (258, "LoongArch"),
+];
+
+/// PE COFF `Machine` values
+const PE_MACHINES: &[(u16, &str)] = &[
+    (0x014C, "x86"),
+    (0x0200, "IA-64"),
+    (0x01C0, "ARM"),
+    (0x01C4, "ARM"),
+    (0x5032, "RISC-V"),
+    (0x5064, "RISC-V"),
+    (0x6264, "LoongArch"),
+    (0x8664, "x86-64"),
+    (0xAA64, "AArch64"),
+];
+
+/// Mach-O `cputype` flag for 64-bit architectures
+const MACHO_ABI64: u32 = 0x0100_0000;
+
+/// Mach-O `cputype` values, without the 64-bit flag
+const MACHO_CPU_TYPES: &[(u32, &str, &str)] = &[
+    (7, "x86", "x86-64"),
+    (12, "ARM", "AArch64"),
+    (18, "PowerPC", "PowerPC64"),
+];
+
+/// Read the format, word size and architecture from an ELF, PE or Mach-O
+/// header (ELF `e_machine`, PE `Machine` and Mach-O `cputype`).
+/// Returns None for other input, including universal (fat) Mach-O binaries.
+pub fn executable_info(bytes: &[u8]) -> Option<ExecutableInfo> {
+    if bytes.starts_with(b"\x7FELF") {
+        elf_info(bytes)
+    } else if bytes.starts_with(b"MZ") {
+        pe_info(bytes)
+    } else {
+        macho_info(bytes)
+    }
+}
+
+fn elf_info(bytes: &[u8]) -> Option<ExecutableInfo> {
+    let bits = match *bytes.get(4)? {
+        1 => Some(32),
+        2 => Some(64),
+        _ => None,
+    };
+    let machine = match *bytes.get(5)? {
+        2 => u16::from_be_bytes(bytes.get(18..20)?.try_into().ok()?),
+        _ => u16::from_le_bytes(bytes.get(18..20)?.try_into().ok()?),
+    };
+
+    Some(ExecutableInfo {
+        format: ExecutableFormat::Elf,
+        bits,
+        architecture: lookup(ELF_MACHINES, machine),
+    })
+}
+
+fn pe_info(bytes: &[u8]) -> Option<ExecutableInfo> {
+    let header = u32::from_le_bytes(bytes.get(0x3C..0x40)?.try_into().ok()?) as usize;
+    if bytes.get(header..header.checked_add(4)?)? != b"PE\0\0" {
+        return None;
+    }
+    let machine = u16::from_le_bytes(bytes.get(header + 4..header + 6)?.try_into().ok()?);
+
+    // The optional header follows the 20-byte COFF header
+    let bits = match bytes.get(header + 24..header + 26) {
+        Some([0x0B, 0x01]) => Some(32),
+        Some([0x0B, 0x02]) => Some(64),
+        _ => None,
+    };
+
+    Some(ExecutableInfo {
+        format: ExecutableFormat::Pe,
+        bits,
+        architecture: lookup(PE_MACHINES, machine),
+    })
+}
+
+fn macho_info(bytes: &[u8]) -> Option<ExecutableInfo> {
+    let (bits, big_endian) = match bytes.get(..4)? {
+        [0xFE, 0xED, 0xFA, 0xCE] => (32, true),
+        [0xCE, 0xFA, 0xED, 0xFE] => (32, false),
+        [0xFE, 0xED, 0xFA, 0xCF] => (64, true),
+        [0xCF, 0xFA, 0xED, 0xFE] => (64, false),
+        _ => return None,
+    };
+    let cputype: [u8; 4] = bytes.get(4..8)?.try_into().ok()?;
+    let cputype = if big_endian { u32::from_be_bytes(cputype) } else { u32::from_le_bytes(cputype) };
+
+    let architecture = MACHO_CPU_TYPES
+        .iter()
+        .find(|(cpu, _, _)| *cpu == cputype & !MACHO_ABI64)
+        .map(|&(_, name, name64)| if cputype & MACHO_ABI64 != 0 { name64 } else { name });
+
+    Some(ExecutableInfo {
+        format: ExecutableFormat::MachO,
+        bits: Some(bits),
+        architecture,
+    })
+}
+
+fn lookup(table: &[(u16, &'static str)], value: u16) -> Option<&'static str> {
+    table.iter().find(|(known, _)| *known == value).map(|&(_, name)| name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elf64_x86_64() {
+        let mut elf = vec![0; 64];
+        elf[..7].copy_from_slice(b"\x7FELF\x02\x01\x01");
+        elf[16..20].copy_from_slice(&[2, 0, 62, 0]);
+
+        let info = executable_info(&elf).unwrap();
+        assert_eq!(
+            info,
+            ExecutableInfo { format: ExecutableFormat::Elf, bits: Some(64), architecture: Some("x86-64") }
+        );
+    }
+
+    #[test]
+    fn pe32_plus_aarch64() {
+        let mut pe = vec![0; 0x80 + 26];
+        pe[..2].copy_from_slice(b"MZ");
+        pe[0x3C..0x40].copy_from_slice(&0x80u32.to_le_bytes());
+        pe[0x80..0x84].copy_from_slice(b"PE\0\0");
+        pe[0x84..0x86].copy_from_slice(&0xAA64u16.to_le_bytes());
+        pe[0x98..0x9A].copy_from_slice(&[0x0B, 0x02]);
+
+        let info = executable_info(&pe).unwrap();
+        assert_eq!(
+            info,
+            ExecutableInfo { format: ExecutableFormat::Pe, bits: Some(64), architecture: Some("AArch64") }
+        );
+    }
+
+    #[test]
+    fn macho64_arm64() {
+        let macho = b"\xCF\xFA\xED\xFE\x0C\x00\x00\x01\x00\x00\x00\x00";
+        let info = executable_info(macho).unwrap();
+        assert_eq!((info.format, info.bits, info.architecture), (ExecutableFormat::MachO, Some(64), Some("AArch64")));
+    }
+
+    #[test]
+    fn dos_stub_without_pe_header() {
+        assert_eq!(executable_info(b"MZ\x90\x00"), None);
+        assert_eq!(executable_info(b"\x89PNG\r\n\x1a\n"), None);
+    }
+}
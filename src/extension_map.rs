@@ -0,0 +1,352 @@
+use std::path::Path;
+
+/// Represents an extension entry with the extension, description, and MIME type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtensionEntry {
+    pub extension: &'static str,
+    pub description: &'static str,
+    pub mime: Option<&'static str>,
+}
+
+/// The built-in extension map. The map is static, so this never allocates;
+/// call `to_vec()` for an owned copy.
+pub fn get_extension_map() -> &'static [ExtensionEntry] {
+    EXTENSION_MAP
+}
+
+/// The built-in extension map. Extensions are lowercase and without the leading dot.
+pub(crate) static EXTENSION_MAP: &[ExtensionEntry] = &[
+    // --- Text and Data Files ---
+    ExtensionEntry { extension: "txt", description: "Plain text file", mime: Some("text/plain") },
+    ExtensionEntry { extension: "log", description: "Log file", mime: Some("text/plain") },
+    ExtensionEntry { extension: "md", description: "Markdown document", mime: Some("text/markdown") },
+    ExtensionEntry { extension: "markdown", description: "Markdown document", mime: Some("text/markdown") },
+    ExtensionEntry { extension: "csv", description: "CSV (comma-separated values) data", mime: Some("text/csv") },
+    ExtensionEntry { extension: "tsv", description: "TSV (tab-separated values) data", mime: Some("text/tab-separated-values") },
+    ExtensionEntry { extension: "json", description: "JSON file", mime: Some("application/json") },
+    ExtensionEntry { extension: "xml", description: "XML document", mime: Some("text/xml") },
+    ExtensionEntry { extension: "yaml", description: "YAML document", mime: Some("application/yaml") },
+    ExtensionEntry { extension: "yml", description: "YAML document", mime: Some("application/yaml") },
+    ExtensionEntry { extension: "toml", description: "TOML document", mime: Some("application/toml") },
+    ExtensionEntry { extension: "ini", description: "INI configuration file", mime: None },
+    ExtensionEntry { extension: "html", description: "HTML document", mime: Some("text/html") },
+    ExtensionEntry { extension: "htm", description: "HTML document", mime: Some("text/html") },
+    ExtensionEntry { extension: "xhtml", description: "XHTML document", mime: Some("application/xhtml+xml") },
+    ExtensionEntry { extension: "rss", description: "RSS feed", mime: Some("application/rss+xml") },
+    ExtensionEntry { extension: "atom", description: "Atom feed", mime: Some("application/atom+xml") },
+    ExtensionEntry { extension: "css", description: "CSS stylesheet", mime: Some("text/css") },
+    ExtensionEntry { extension: "svg", description: "Scalable Vector Graphics (SVG)", mime: Some("image/svg+xml") },
+
+    // --- Source Code and Scripts ---
+    ExtensionEntry { extension: "rs", description: "Rust source code", mime: Some("text/x-rust") },
+    ExtensionEntry { extension: "c", description: "C source code", mime: Some("text/x-c") },
+    ExtensionEntry { extension: "h", description: "C header file", mime: Some("text/x-c") },
+    ExtensionEntry { extension: "cpp", description: "C++ source code", mime: Some("text/x-c++") },
+    ExtensionEntry { extension: "java", description: "Java source code", mime: Some("text/x-java") },
+    ExtensionEntry { extension: "go", description: "Go source code", mime: Some("text/x-go") },
+    ExtensionEntry { extension: "py", description: "Python script", mime: Some("text/x-python") },
+    ExtensionEntry { extension: "js", description: "JavaScript source code", mime: Some("text/javascript") },
+    ExtensionEntry { extension: "ts", description: "TypeScript source code", mime: Some("application/typescript") },
+    ExtensionEntry { extension: "sh", description: "Shell script", mime: Some("text/x-shellscript") },
+    ExtensionEntry { extension: "sql", description: "SQL script", mime: Some("application/sql") },
+
+    // --- Image Files ---
+    ExtensionEntry { extension: "png", description: "PNG image", mime: Some("image/png") },
+    ExtensionEntry { extension: "jpg", description: "JPEG image", mime: Some("image/jpeg") },
+    ExtensionEntry { extension: "jpeg", description: "JPEG image", mime: Some("image/jpeg") },
+    ExtensionEntry { extension: "jpe", description: "JPEG image", mime: Some("image/jpeg") },
+    ExtensionEntry { extension: "jfif", description: "JPEG image", mime: Some("image/jpeg") },
+    ExtensionEntry { extension: "gif", description: "GIF image", mime: Some("image/gif") },
+    ExtensionEntry { extension: "bmp", description: "BMP image", mime: Some("image/bmp") },
+    ExtensionEntry { extension: "webp", description: "WebP image", mime: Some("image/webp") },
+    ExtensionEntry { extension: "tiff", description: "TIFF image", mime: Some("image/tiff") },
+    ExtensionEntry { extension: "tif", description: "TIFF image", mime: Some("image/tiff") },
+    ExtensionEntry { extension: "ico", description: "ICO icon", mime: Some("image/vnd.microsoft.icon") },
+    ExtensionEntry { extension: "psd", description: "PSD image, Adobe Photoshop", mime: Some("image/vnd.adobe.photoshop") },
+
+    // --- Documents ---
+    ExtensionEntry { extension: "pdf", description: "PDF document", mime: Some("application/pdf") },
+    ExtensionEntry { extension: "rtf", description: "Rich Text Format (RTF) data", mime: Some("text/rtf") },
+    ExtensionEntry { extension: "doc", description: "Microsoft Word document", mime: Some("application/msword") },
+    ExtensionEntry { extension: "xls", description: "Microsoft Excel spreadsheet", mime: Some("application/vnd.ms-excel") },
+    ExtensionEntry { extension: "ppt", description: "Microsoft PowerPoint presentation", mime: Some("application/vnd.ms-powerpoint") },
+    ExtensionEntry { extension: "docx", description: "Microsoft Word 2007+ document (DOCX)", mime: Some("application/vnd.openxmlformats-officedocument.wordprocessingml.document") },
+    ExtensionEntry { extension: "xlsx", description: "Microsoft Excel 2007+ spreadsheet (XLSX)", mime: Some("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet") },
+    ExtensionEntry { extension: "pptx", description: "Microsoft PowerPoint 2007+ presentation (PPTX)", mime: Some("application/vnd.openxmlformats-officedocument.presentationml.presentation") },
+    ExtensionEntry { extension: "odt", description: "OpenDocument Text (ODT)", mime: Some("application/vnd.oasis.opendocument.text") },
+    ExtensionEntry { extension: "epub", description: "EPUB e-book", mime: Some("application/epub+zip") },
+
+    // --- Archives and Compressed Files ---
+    ExtensionEntry { extension: "zip", description: "Zip archive", mime: Some("application/zip") },
+    ExtensionEntry { extension: "jar", description: "Java archive (JAR)", mime: Some("application/java-archive") },
+    ExtensionEntry { extension: "tar", description: "tar archive", mime: Some("application/x-tar") },
+    ExtensionEntry { extension: "gz", description: "gzip compressed data", mime: Some("application/gzip") },
+    ExtensionEntry { extension: "bz2", description: "bzip2 compressed data", mime: Some("application/x-bzip2") },
+    ExtensionEntry { extension: "xz", description: "xz compressed data", mime: Some("application/x-xz") },
+    ExtensionEntry { extension: "zst", description: "Zstandard compressed data", mime: Some("application/zstd") },
+    ExtensionEntry { extension: "7z", description: "7-zip archive", mime: Some("application/x-7z-compressed") },
+    ExtensionEntry { extension: "rar", description: "RAR archive", mime: Some("application/vnd.rar") },
+
+    // --- Audio and Video Files ---
+    ExtensionEntry { extension: "mp3", description: "MP3 audio", mime: Some("audio/mpeg") },
+    ExtensionEntry { extension: "wav", description: "WAVE audio", mime: Some("audio/x-wav") },
+    ExtensionEntry { extension: "flac", description: "FLAC audio", mime: Some("audio/x-flac") },
+    ExtensionEntry { extension: "ogg", description: "Ogg audio", mime: Some("application/ogg") },
+    ExtensionEntry { extension: "mp4", description: "MP4 video", mime: Some("video/mp4") },
+    ExtensionEntry { extension: "mkv", description: "Matroska video", mime: Some("video/x-matroska") },
+    ExtensionEntry { extension: "webm", description: "WebM video", mime: Some("video/webm") },
+    ExtensionEntry { extension: "avi", description: "AVI video", mime: Some("video/x-msvideo") },
+    ExtensionEntry { extension: "mov", description: "QuickTime video", mime: Some("video/quicktime") },
+
+    // --- Executables and Libraries ---
+    ExtensionEntry { extension: "exe", description: "Windows executable", mime: Some("application/vnd.microsoft.portable-executable") },
+    ExtensionEntry { extension: "dll", description: "Windows dynamic-link library", mime: Some("application/vnd.microsoft.portable-executable") },
+    ExtensionEntry { extension: "so", description: "ELF shared object", mime: Some("application/x-executable") },
+    ExtensionEntry { extension: "class", description: "Java class file", mime: Some("application/java-vm") },
+    ExtensionEntry { extension: "wasm", description: "WebAssembly binary", mime: Some("application/wasm") },
+
+    // --- Fonts and Databases ---
+    ExtensionEntry { extension: "ttf", description: "TrueType Font file", mime: Some("application/font-sfnt") },
+    ExtensionEntry { extension: "otf", description: "OpenType Font file", mime: Some("application/font-sfnt") },
+    ExtensionEntry { extension: "woff", description: "Web Open Font Format 1.0", mime: Some("application/font-woff") },
+    ExtensionEntry { extension: "woff2", description: "Web Open Font Format 2.0", mime: Some("application/font-woff") },
+    ExtensionEntry { extension: "sqlite", description: "SQLite database file", mime: Some("application/vnd.sqlite3") },
+    ExtensionEntry { extension: "db", description: "Database file", mime: None },
+];
+
+/// MIME types of formats that are stored inside a generic container format,
+/// keyed by the container's MIME type.
+const CONTAINER_FORMATS: &[(&str, &[&str])] = &[
+    ("application/zip", &[
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        "application/vnd.oasis.opendocument.text",
+        "application/vnd.oasis.opendocument.spreadsheet",
+        "application/vnd.oasis.opendocument.presentation",
+        "application/epub+zip",
+        "application/java-archive",
+        "application/vnd.android.package-archive",
+    ]),
+    ("application/x-ole-storage", &[
+        "application/msword",
+        "application/vnd.ms-excel",
+        "application/vnd.ms-powerpoint",
+    ]),
+    ("video/x-matroska", &["video/webm"]),
+];
+
+/// Canonical extension for each MIME type this crate can report. Where a type
+/// has several extensions, the most common one is listed.
+const MIME_EXTENSIONS: &[(&str, &str)] = &[
+    // --- Text and Data Files ---
+    ("text/plain", "txt"),
+    ("text/markdown", "md"),
+    ("text/csv", "csv"),
+    ("text/tab-separated-values", "tsv"),
+    ("text/html", "html"),
+    ("application/xhtml+xml", "xhtml"),
+    ("application/rss+xml", "rss"),
+    ("application/atom+xml", "atom"),
+    ("text/css", "css"),
+    ("text/xml", "xml"),
+    ("application/xml", "xml"),
+    ("application/json", "json"),
+    ("application/yaml", "yaml"),
+    ("application/toml", "toml"),
+    ("text/rtf", "rtf"),
+    ("application/rtf", "rtf"),
+
+    // --- Source Code and Scripts ---
+    ("text/x-rust", "rs"),
+    ("text/x-c", "c"),
+    ("text/x-c++", "cpp"),
+    ("text/x-java", "java"),
+    ("text/x-go", "go"),
+    ("text/x-python", "py"),
+    ("text/javascript", "js"),
+    ("application/typescript", "ts"),
+    ("text/x-shellscript", "sh"),
+    ("text/x-perl", "pl"),
+    ("text/x-ruby", "rb"),
+    ("application/x-httpd-php", "php"),
+    ("text/x-lua", "lua"),
+    ("text/x-tcl", "tcl"),
+    ("text/x-awk", "awk"),
+    ("text/x-r", "R"),
+    ("application/sql", "sql"),
+
+    // --- Image Files ---
+    ("image/png", "png"),
+    ("image/jpeg", "jpg"),
+    ("image/gif", "gif"),
+    ("image/bmp", "bmp"),
+    ("image/webp", "webp"),
+    ("image/tiff", "tif"),
+    ("image/vnd.microsoft.icon", "ico"),
+    ("image/x-icon", "ico"),
+    ("image/vnd.adobe.photoshop", "psd"),
+    ("image/svg+xml", "svg"),
+    ("image/jp2", "jp2"),
+    ("image/heif", "heif"),
+    ("image/avif", "avif"),
+    ("image/x-xcf", "xcf"),
+    ("image/vnd.dwg", "dwg"),
+    ("image/wmf", "wmf"),
+    ("image/fits", "fits"),
+    ("image/cineon", "cin"),
+    ("image/x-jng", "jng"),
+    ("image/x-sun-raster", "ras"),
+    ("image/x-win-bitmap", "cur"),
+
+    // --- Documents ---
+    ("application/pdf", "pdf"),
+    ("application/postscript", "ps"),
+    ("application/illustrator", "ai"),
+    ("application/msword", "doc"),
+    ("application/vnd.ms-excel", "xls"),
+    ("application/vnd.ms-powerpoint", "ppt"),
+    ("application/vnd.ms-outlook", "msg"),
+    ("application/vnd.openxmlformats-officedocument.wordprocessingml.document", "docx"),
+    ("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet", "xlsx"),
+    ("application/vnd.openxmlformats-officedocument.presentationml.presentation", "pptx"),
+    ("application/vnd.oasis.opendocument.text", "odt"),
+    ("application/vnd.oasis.opendocument.spreadsheet", "ods"),
+    ("application/vnd.oasis.opendocument.presentation", "odp"),
+    ("application/epub+zip", "epub"),
+
+    // --- Archives and Compressed Files ---
+    ("application/zip", "zip"),
+    ("application/java-archive", "jar"),
+    ("application/vnd.android.package-archive", "apk"),
+    ("application/x-google-chrome-extension", "crx"),
+    ("application/x-tar", "tar"),
+    ("application/gzip", "gz"),
+    ("application/x-bzip2", "bz2"),
+    ("application/x-xz", "xz"),
+    ("application/zstd", "zst"),
+    ("application/x-lz4", "lz4"),
+    ("application/x-compress", "Z"),
+    ("application/x-7z-compressed", "7z"),
+    ("application/vnd.rar", "rar"),
+    ("application/x-cpio", "cpio"),
+    ("application/x-zoo", "zoo"),
+    ("application/x-unix-archive", "a"),
+    ("application/x-rpm", "rpm"),
+    ("application/vnd.debian.binary-package", "deb"),
+    ("application/vnd.ms-cab-compressed", "cab"),
+    ("application/x-iso9660-image", "iso"),
+    ("application/x-apple-diskimage", "dmg"),
+
+    // --- Audio and Video Files ---
+    ("audio/mpeg", "mp3"),
+    ("audio/x-wav", "wav"),
+    ("audio/x-flac", "flac"),
+    ("audio/aac", "aac"),
+    ("audio/midi", "mid"),
+    ("audio/basic", "au"),
+    ("audio/x-pn-realaudio", "ra"),
+    ("audio/ogg", "ogg"),
+    ("application/ogg", "ogg"),
+    ("video/mp4", "mp4"),
+    ("video/x-matroska", "mkv"),
+    ("video/webm", "webm"),
+    ("video/x-msvideo", "avi"),
+    ("video/quicktime", "mov"),
+    ("video/mpeg", "mpg"),
+    ("video/mp2t", "ts"),
+    ("video/3gpp", "3gp"),
+    ("video/x-flv", "flv"),
+    ("video/x-ms-asf", "asf"),
+    ("video/x-mng", "mng"),
+
+    // --- Executables and Libraries ---
+    ("application/vnd.microsoft.portable-executable", "exe"),
+    ("application/x-executable", "elf"),
+    ("application/x-sharedlib", "so"),
+    ("application/x-mach-binary", "dylib"),
+    ("application/java-vm", "class"),
+    ("application/vnd.android.dex", "dex"),
+    ("application/wasm", "wasm"),
+    ("application/x-ms-shortcut", "lnk"),
+
+    // --- Fonts, Databases and Other Data ---
+    ("application/font-sfnt", "ttf"),
+    ("font/ttf", "ttf"),
+    ("font/otf", "otf"),
+    ("font/collection", "ttc"),
+    ("application/font-woff", "woff"),
+    ("font/woff", "woff"),
+    ("font/woff2", "woff2"),
+    ("application/vnd.sqlite3", "sqlite"),
+    ("application/x-msaccess", "mdb"),
+    ("application/dicom", "dcm"),
+    ("application/x-pem-file", "pem"),
+    ("application/x-x509-ca-cert", "crt"),
+];
+
+/// Look up the extension entry for a path's extension, ignoring case.
+pub(crate) fn lookup(path: &Path) -> Option<&'static ExtensionEntry> {
+    find(path.extension()?.to_str()?)
+}
+
+/// Describe a file extension, e.g. "JPEG image" for "jpg", "JPG" or "jpeg".
+/// Matching ignores case and a leading dot.
+pub fn describe_extension(ext: &str) -> Option<&'static str> {
+    find(ext.strip_prefix('.').unwrap_or(ext)).map(|entry| entry.description)
+}
+
+/// The entry for an extension given without the leading dot, ignoring case.
+fn find(ext: &str) -> Option<&'static ExtensionEntry> {
+    EXTENSION_MAP.iter().find(|entry| entry.extension.eq_ignore_ascii_case(ext))
+}
+
+/// Whether `mime` is a format stored inside the container format `container_mime`.
+pub(crate) fn is_contained_in(mime: &str, container_mime: &str) -> bool {
+    CONTAINER_FORMATS
+        .iter()
+        .any(|(container, formats)| *container == container_mime && formats.contains(&mime))
+}
+
+/// The canonical file extension, without the leading dot, for a MIME type.
+/// For types with several common extensions the most common one is returned,
+/// e.g. "jpg" for `image/jpeg`. Returns None for unknown MIME types.
+pub fn extension_for_mime(mime: &str) -> Option<&'static str> {
+    MIME_EXTENSIONS
+        .iter()
+        .find(|(known, _)| *known == mime)
+        .map(|(_, extension)| *extension)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn common_extension_for_mime() {
+        assert_eq!(extension_for_mime("image/jpeg"), Some("jpg"));
+        assert_eq!(extension_for_mime("image/png"), Some("png"));
+        assert_eq!(extension_for_mime("application/zip"), Some("zip"));
+        assert_eq!(extension_for_mime("application/pdf"), Some("pdf"));
+    }
+
+    #[test]
+    fn unknown_mime_has_no_extension() {
+        assert_eq!(extension_for_mime("application/x-not-a-real-type"), None);
+    }
+
+    #[test]
+    fn jpeg_spellings_and_cases_agree() {
+        for ext in ["JPG", "jpg", "jpeg", "Jpeg", ".jpg"] {
+            assert_eq!(describe_extension(ext), Some("JPEG image"));
+        }
+        assert_eq!(lookup(Path::new("photo.JPG")).unwrap().mime, Some("image/jpeg"));
+        assert_eq!(describe_extension("nope"), None);
+    }
+
+    #[test]
+    fn extension_map_is_static() {
+        assert!(std::ptr::eq(get_extension_map(), get_extension_map()));
+        assert!(get_extension_map().iter().all(|entry| entry.extension == entry.extension.to_ascii_lowercase()));
+    }
+}
@@ -0,0 +1,208 @@
+/// Structured file type, for matching on detected types without comparing
+/// description strings.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FileType {
+    Directory,
+    Symlink,
+    BrokenSymlink,
+    NamedPipe,
+    Socket,
+    BlockDevice,
+    CharDevice,
+    /// A Windows reparse point other than a symbolic link or junction, such
+    /// as an app execution alias, that can't be opened as a file
+    ReparsePoint,
+    Empty,
+
+    // --- Images ---
+    Png,
+    Jpeg,
+    Gif,
+    Bmp,
+    Tiff,
+    Webp,
+    Ico,
+    Svg,
+    Psd,
+    Heif,
+    Avif,
+
+    // --- Audio and Video ---
+    Mp3,
+    Wav,
+    Flac,
+    Ogg,
+    Aac,
+    Midi,
+    Mp4,
+    Matroska,
+    Webm,
+    Avi,
+    QuickTime,
+    Mpeg,
+
+    // --- Documents ---
+    Pdf,
+    PostScript,
+    Rtf,
+    Doc,
+    Xls,
+    Ppt,
+    Docx,
+    Xlsx,
+    Pptx,
+    Odt,
+    Ods,
+    Odp,
+    Epub,
+
+    // --- Archives and Compressed Files ---
+    Zip,
+    Jar,
+    Apk,
+    Tar,
+    Gzip,
+    Bzip2,
+    Xz,
+    Zstd,
+    SevenZip,
+    Rar,
+    Cab,
+    Iso,
+    Deb,
+    Rpm,
+
+    // --- Executables ---
+    Elf,
+    Pe,
+    MachO,
+    Wasm,
+    JavaClass,
+    Dex,
+
+    // --- Text, Data and Fonts ---
+    PlainText,
+    Script,
+    Html,
+    Xml,
+    Json,
+    Sqlite,
+    Font,
+
+    /// A type without a dedicated variant, or an unrecognized file;
+    /// holds the description
+    Unknown(String),
+    /// A match against an entry registered in a `MagicRegistry`; holds the
+    /// entry's description
+    Custom(String),
+}
+
+impl FileType {
+    /// The structured type for a detected MIME type and description, falling
+    /// back to `Unknown` with the description when no variant fits.
+    pub(crate) fn detected(mime: Option<&str>, description: &str) -> Self {
+        mime.and_then(from_mime)
+            .unwrap_or_else(|| FileType::Unknown(description.to_string()))
+    }
+}
+
+fn from_mime(mime: &str) -> Option<FileType> {
+    Some(match mime {
+        "image/png" => FileType::Png,
+        "image/jpeg" => FileType::Jpeg,
+        "image/gif" => FileType::Gif,
+        "image/bmp" => FileType::Bmp,
+        "image/tiff" => FileType::Tiff,
+        "image/webp" => FileType::Webp,
+        "image/vnd.microsoft.icon" | "image/x-icon" => FileType::Ico,
+        "image/svg+xml" => FileType::Svg,
+        "image/vnd.adobe.photoshop" => FileType::Psd,
+        "image/heif" => FileType::Heif,
+        "image/avif" => FileType::Avif,
+
+        "audio/mpeg" => FileType::Mp3,
+        "audio/x-wav" => FileType::Wav,
+        "audio/x-flac" => FileType::Flac,
+        "application/ogg" | "audio/ogg" => FileType::Ogg,
+        "audio/aac" => FileType::Aac,
+        "audio/midi" => FileType::Midi,
+        "video/mp4" => FileType::Mp4,
+        "video/x-matroska" => FileType::Matroska,
+        "video/webm" => FileType::Webm,
+        "video/x-msvideo" => FileType::Avi,
+        "video/quicktime" => FileType::QuickTime,
+        "video/mpeg" => FileType::Mpeg,
+
+        "application/pdf" => FileType::Pdf,
+        "application/postscript" => FileType::PostScript,
+        "text/rtf" | "application/rtf" => FileType::Rtf,
+        "application/msword" => FileType::Doc,
+        "application/vnd.ms-excel" => FileType::Xls,
+        "application/vnd.ms-powerpoint" => FileType::Ppt,
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document" => FileType::Docx,
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet" => FileType::Xlsx,
+        "application/vnd.openxmlformats-officedocument.presentationml.presentation" => FileType::Pptx,
+        "application/vnd.oasis.opendocument.text" => FileType::Odt,
+        "application/vnd.oasis.opendocument.spreadsheet" => FileType::Ods,
+        "application/vnd.oasis.opendocument.presentation" => FileType::Odp,
+        "application/epub+zip" => FileType::Epub,
+
+        "application/zip" => FileType::Zip,
+        "application/java-archive" => FileType::Jar,
+        "application/vnd.android.package-archive" => FileType::Apk,
+        "application/x-tar" => FileType::Tar,
+        "application/gzip" => FileType::Gzip,
+        "application/x-bzip2" => FileType::Bzip2,
+        "application/x-xz" => FileType::Xz,
+        "application/zstd" => FileType::Zstd,
+        "application/x-7z-compressed" => FileType::SevenZip,
+        "application/vnd.rar" => FileType::Rar,
+        "application/vnd.ms-cab-compressed" => FileType::Cab,
+        "application/x-iso9660-image" => FileType::Iso,
+        "application/vnd.debian.binary-package" => FileType::Deb,
+        "application/x-rpm" => FileType::Rpm,
+
+        "application/x-executable" | "application/x-sharedlib" => FileType::Elf,
+        "application/vnd.microsoft.portable-executable" => FileType::Pe,
+        "application/x-mach-binary" => FileType::MachO,
+        "application/wasm" => FileType::Wasm,
+        "application/java-vm" => FileType::JavaClass,
+        "application/vnd.android.dex" => FileType::Dex,
+
+        "text/plain" => FileType::PlainText,
+        "text/x-shellscript" | "text/x-python" | "text/x-perl" | "text/x-ruby" | "text/x-lua"
+        | "text/x-tcl" | "text/x-awk" | "text/x-r" | "text/javascript" | "application/x-httpd-php" => FileType::Script,
+        "text/html" => FileType::Html,
+        "text/xml" | "application/xml" => FileType::Xml,
+        "application/json" => FileType::Json,
+        "application/vnd.sqlite3" => FileType::Sqlite,
+        _ if mime.starts_with("font/") || mime.starts_with("application/font-") => FileType::Font,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{identify_from_bytes, identify_many_bytes};
+
+    #[test]
+    fn zip_has_zip_type() {
+        assert_eq!(identify_from_bytes(b"PK\x03\x04\x14\x00\x00\x00").unwrap().file_type, FileType::Zip);
+    }
+
+    #[test]
+    fn unrecognized_blob_is_unknown() {
+        let info = &identify_many_bytes([&[0x00, 0xDE, 0xAD, 0x00][..]])[0];
+        assert!(matches!(info.file_type, FileType::Unknown(_)));
+    }
+
+    #[test]
+    fn every_shebang_mime_is_a_script() {
+        for script in [&b"#!/bin/sh\n"[..], b"#!/usr/bin/env python3\n", b"#!/usr/bin/env node\n", b"#!/usr/bin/php\n"] {
+            assert_eq!(identify_from_bytes(script).unwrap().file_type, FileType::Script);
+        }
+    }
+}
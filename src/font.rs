@@ -0,0 +1,72 @@
+//! Details of font files beyond their type.
+
+/// Container format of a font file, from `font_info`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FontFormat {
+    /// sfnt font with TrueType outlines (.ttf)
+    TrueType,
+    /// sfnt font with CFF outlines (.otf)
+    OpenType,
+    /// WOFF 1.0 compressed sfnt (.woff)
+    Woff,
+    /// WOFF 2.0 compressed sfnt (.woff2)
+    Woff2,
+}
+
+/// Header fields of a font file, from `font_info`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FontInfo {
+    pub format: FontFormat,
+    /// Number of font tables listed in the header, None if it is truncated
+    pub num_tables: Option<u16>,
+}
+
+/// Recognize a TrueType, OpenType, WOFF or WOFF2 font and read the number of
+/// tables from its header.
+/// Returns None for other input, including font collections (.ttc).
+pub fn font_info(bytes: &[u8]) -> Option<FontInfo> {
+    // sfnt fonts store numTables after the 4-byte version; WOFF headers after
+    // the signature, flavor and total length
+    let (format, num_tables_at) = match bytes.get(..4)? {
+        [0x00, 0x01, 0x00, 0x00] => (FontFormat::TrueType, 4),
+        b"OTTO" => (FontFormat::OpenType, 4),
+        b"wOFF" => (FontFormat::Woff, 12),
+        b"wOF2" => (FontFormat::Woff2, 12),
+        _ => return None,
+    };
+
+    let num_tables = bytes
+        .get(num_tables_at..num_tables_at + 2)
+        .map(|field| u16::from_be_bytes([field[0], field[1]]));
+
+    Some(FontInfo { format, num_tables })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_signature() {
+        let fonts: [(&[u8], FontFormat, &str); 4] = [
+            (b"\x00\x01\x00\x00\x00\x0b\x00\x80", FontFormat::TrueType, "TrueType Font file (.ttf)"),
+            (b"OTTO\x00\x0b\x00\x80", FontFormat::OpenType, "OpenType Font file (.otf)"),
+            (b"wOFF\x00\x01\x00\x00\x00\x00\x10\x00\x00\x0b", FontFormat::Woff, "Web Open Font Format 1.0 (.woff)"),
+            (b"wOF2\x00\x01\x00\x00\x00\x00\x10\x00\x00\x0b", FontFormat::Woff2, "Web Open Font Format 2.0 (.woff2)"),
+        ];
+
+        for (bytes, format, description) in fonts {
+            assert_eq!(font_info(bytes), Some(FontInfo { format, num_tables: Some(11) }));
+            assert_eq!(crate::identify_from_bytes(bytes).unwrap().description, description);
+        }
+    }
+
+    #[test]
+    fn truncated_and_other_input() {
+        assert_eq!(font_info(b"OTTO"), Some(FontInfo { format: FontFormat::OpenType, num_tables: None }));
+        assert_eq!(font_info(b"ttcf\x00\x01\x00\x00"), None);
+        assert_eq!(font_info(b"\x89PNG\r\n\x1a\n"), None);
+    }
+}
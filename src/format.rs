@@ -0,0 +1,62 @@
+//! Human-readable formatting of file sizes.
+
+/// Format a size in bytes using binary units, e.g. "1.5 KiB" for 1536.
+/// Sizes below 1024 are shown as whole bytes, e.g. "1023 B".
+pub fn format_size(bytes: u64) -> String {
+    format_with_units(bytes, 1024.0, &["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"])
+}
+
+/// Format a size in bytes using decimal (SI) units, e.g. "1.5 KB" for 1500.
+/// Sizes below 1000 are shown as whole bytes, e.g. "999 B".
+pub fn format_size_si(bytes: u64) -> String {
+    format_with_units(bytes, 1000.0, &["KB", "MB", "GB", "TB", "PB", "EB"])
+}
+
+fn format_with_units(bytes: u64, base: f64, units: &[&str]) -> String {
+    if (bytes as f64) < base {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64;
+    let mut unit = units[0];
+    for next in units {
+        value /= base;
+        unit = next;
+        // Stop at the largest unit whose rounded value stays below `base`
+        if (value * 10.0).round() / 10.0 < base {
+            break;
+        }
+    }
+
+    format!("{value:.1} {unit}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{create_directory_info, create_unknown_info};
+
+    #[test]
+    fn binary_boundaries() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1024), "1.0 KiB");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(1024 * 1024), "1.0 MiB");
+    }
+
+    #[test]
+    fn decimal_boundaries() {
+        assert_eq!(format_size_si(999), "999 B");
+        assert_eq!(format_size_si(1000), "1.0 KB");
+        assert_eq!(format_size_si(1536), "1.5 KB");
+        assert_eq!(format_size_si(999_999), "1.0 MB");
+    }
+
+    #[test]
+    fn size_display_for_files_and_directories() {
+        assert_eq!(create_unknown_info("file", 1536).size_display(), "1.5 KiB");
+        assert_eq!(create_directory_info("dir").size_display(), "-");
+    }
+}
@@ -0,0 +1,74 @@
+//! Recursive traversal that honors ignore files, enabled by the `gitignore` feature.
+
+use std::path::Path;
+
+use ignore::WalkBuilder;
+
+use crate::{FileInfo, FileProcessingError, create_symlink_info, identify_file_from_path};
+
+/// Recursively process a directory, skipping paths excluded by `.gitignore` and
+/// `.ignore` files the way git would, including ignore files in parent directories.
+/// Ignore files are honored even outside a git repository.
+///
+/// This only changes which entries are visited, not how they're identified.
+/// Hidden files are still visited unless ignored, except for `.git`
+/// directories, which git never tracks; symbolic links are reported as
+/// "Symbolic link" as with `identify_recursive`.
+pub fn identify_recursive_gitignore<P: AsRef<Path>>(path: P) -> Result<Vec<FileInfo>, FileProcessingError> {
+    let path = path.as_ref();
+
+    if !path.exists() {
+        return Err(FileProcessingError::PathNotFound(path.to_path_buf()));
+    }
+
+    let walker = WalkBuilder::new(path)
+        .hidden(false)
+        .require_git(false)
+        .filter_entry(|entry| {
+            entry.depth() == 0
+                || entry.file_name() != ".git"
+                || !entry.file_type().is_some_and(|file_type| file_type.is_dir())
+        })
+        .build();
+
+    let mut results = Vec::new();
+    for entry in walker {
+        let entry = entry?;
+        let file_info = if entry.path_is_symlink() {
+            create_symlink_info(entry.path())
+        } else {
+            identify_file_from_path(entry.path())?
+        };
+        results.push(file_info);
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    #[test]
+    fn ignored_and_git_directories_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        fs::create_dir_all(root.join("target/debug")).unwrap();
+        fs::write(root.join("target/debug/app"), b"\x7fELF").unwrap();
+        fs::create_dir(root.join(".git")).unwrap();
+        fs::write(root.join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+        fs::create_dir(root.join("src")).unwrap();
+        fs::write(root.join("src/main.rs"), "fn main() {}\n").unwrap();
+
+        let mut names: Vec<String> = identify_recursive_gitignore(root)
+            .unwrap()
+            .iter()
+            .map(|info| info.path.strip_prefix(root).unwrap().to_string_lossy().replace('\\', "/"))
+            .collect();
+        names.sort();
+        assert_eq!(names, ["", ".gitignore", "src", "src/main.rs"]);
+    }
+}
@@ -0,0 +1,106 @@
+//! Content hashing alongside identification, enabled by the `hashing` feature.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use sha2::{Digest, Sha256};
+
+use crate::{FileInfo, FileProcessingError, header_read_len, identify_header, with_metadata};
+
+/// Identify a file and compute the SHA-256 hash of its full contents.
+/// The file is read once; identification uses the same header bytes as
+/// `identify_multiple`. Returns the hash as a lowercase hex string.
+/// Fails with `NotAFile` for directories, named pipes and other special files,
+/// which are never opened: they have no contents to hash, or reading them may block.
+pub fn identify_file_with_hash(path: &Path) -> Result<(FileInfo, String), FileProcessingError> {
+    let metadata = fs::metadata(path)?;
+    if !metadata.is_file() {
+        return Err(FileProcessingError::NotAFile(path.to_path_buf()));
+    }
+
+    let bytes = fs::read(path)?;
+    let header = &bytes[..bytes.len().min(header_read_len())];
+
+    let file_info = with_metadata(identify_header(path, header, &bytes, bytes.len() as u64), &metadata);
+
+    Ok((file_info, to_hex(&Sha256::digest(&bytes))))
+}
+
+/// Group files with identical content hashes, as returned by
+/// `identify_file_with_hash`. Only groups with more than one member are returned.
+/// Members are sorted by path and groups by their first member's path, so the
+/// output is the same regardless of input order.
+pub fn find_duplicates(results_with_hashes: &[(FileInfo, String)]) -> Vec<Vec<FileInfo>> {
+    let mut by_hash: BTreeMap<&str, Vec<FileInfo>> = BTreeMap::new();
+    for (file_info, hash) in results_with_hashes {
+        by_hash.entry(hash).or_default().push(file_info.clone());
+    }
+
+    let mut groups: Vec<Vec<FileInfo>> = by_hash
+        .into_values()
+        .filter(|group| group.len() > 1)
+        .map(|mut group| {
+            group.sort_by(|a, b| a.path.cmp(&b.path));
+            group
+        })
+        .collect();
+    groups.sort_by(|a, b| a[0].path.cmp(&b[0].path));
+    groups
+}
+
+/// Encode bytes as a lowercase hex string.
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashes_known_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("abc.txt");
+        fs::write(&path, b"abc").unwrap();
+
+        let (info, hash) = identify_file_with_hash(&path).unwrap();
+        assert_eq!(hash, "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_eq!(info.description, "ASCII text");
+        assert_eq!(info.size, Some(3));
+    }
+
+    #[test]
+    fn refuses_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(matches!(identify_file_with_hash(dir.path()), Err(FileProcessingError::NotAFile(_))));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn refuses_fifos_without_blocking() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pipe");
+        assert!(std::process::Command::new("mkfifo").arg(&path).status().unwrap().success());
+        assert!(matches!(identify_file_with_hash(&path), Err(FileProcessingError::NotAFile(_))));
+    }
+
+    #[test]
+    fn identical_files_form_one_group() {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, content) in [("b.txt", "same"), ("unique.txt", "different"), ("a.txt", "same")] {
+            fs::write(dir.path().join(name), content).unwrap();
+        }
+
+        let hashed: Vec<(FileInfo, String)> = ["b.txt", "unique.txt", "a.txt"]
+            .iter()
+            .map(|name| identify_file_with_hash(&dir.path().join(name)).unwrap())
+            .collect();
+        let groups = find_duplicates(&hashed);
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].len(), 2);
+        assert_eq!(groups[0][0].path, dir.path().join("a.txt"));
+        assert_eq!(groups[0][1].path, dir.path().join("b.txt"));
+    }
+}
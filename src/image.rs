@@ -0,0 +1,169 @@
+/// Extract the pixel dimensions of a PNG, GIF, or JPEG image from its header.
+/// Returns `(width, height)`, or None for other formats and truncated headers.
+pub fn dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        png_dimensions(bytes)
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        gif_dimensions(bytes)
+    } else if bytes.starts_with(&[0xFF, 0xD8]) {
+        jpeg_dimensions(bytes)
+    } else {
+        None
+    }
+}
+
+/// Width and height from the IHDR chunk, which must directly follow the signature.
+fn png_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    if bytes.get(12..16)? != b"IHDR" {
+        return None;
+    }
+    Some((read_u32_be(bytes, 16)?, read_u32_be(bytes, 20)?))
+}
+
+/// Width and height from the logical screen descriptor.
+fn gif_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    let width = u16::from_le_bytes(bytes.get(6..8)?.try_into().ok()?);
+    let height = u16::from_le_bytes(bytes.get(8..10)?.try_into().ok()?);
+    Some((width.into(), height.into()))
+}
+
+/// Width and height from the first start-of-frame (SOFn) segment.
+fn jpeg_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    // SOF0-SOF15, excluding DHT (C4), JPG (C8) and DAC (CC)
+    let pos = jpeg_segment(bytes, |marker, _| {
+        matches!(marker, 0xC0..=0xCF) && !matches!(marker, 0xC4 | 0xC8 | 0xCC)
+    })?;
+    let height = read_u16_be(bytes, pos + 3)?;
+    let width = read_u16_be(bytes, pos + 5)?;
+    Some((width.into(), height.into()))
+}
+
+/// The EXIF Orientation tag of a JPEG image, 1 to 8, describing how the stored
+/// image must be rotated or flipped for display (1 means as stored).
+/// Returns None if the input isn't a JPEG or has no EXIF orientation.
+pub fn jpeg_orientation(bytes: &[u8]) -> Option<u16> {
+    if !bytes.starts_with(&[0xFF, 0xD8]) {
+        return None;
+    }
+
+    // APP1 segments also hold XMP, so look for the one carrying EXIF
+    let pos = jpeg_segment(bytes, |marker, pos| {
+        marker == 0xE1 && bytes.get(pos + 2..pos + 8) == Some(&EXIF_HEADER[..])
+    })?;
+    exif_orientation(bytes.get(pos + 8..)?)
+}
+
+const EXIF_HEADER: [u8; 6] = *b"Exif\0\0";
+const EXIF_ORIENTATION_TAG: u16 = 0x0112;
+
+/// The Orientation tag from IFD0 of the TIFF structure in an EXIF segment.
+fn exif_orientation(tiff: &[u8]) -> Option<u16> {
+    let big_endian = match tiff.get(..4)? {
+        b"MM\0*" => true,
+        b"II*\0" => false,
+        _ => return None,
+    };
+    let read_u16 = |offset: usize| -> Option<u16> {
+        let field = tiff.get(offset..offset + 2)?.try_into().ok()?;
+        Some(if big_endian { u16::from_be_bytes(field) } else { u16::from_le_bytes(field) })
+    };
+    let read_u32 = |offset: usize| -> Option<u32> {
+        let field = tiff.get(offset..offset + 4)?.try_into().ok()?;
+        Some(if big_endian { u32::from_be_bytes(field) } else { u32::from_le_bytes(field) })
+    };
+
+    let ifd = usize::try_from(read_u32(4)?).ok()?;
+    let count = usize::from(read_u16(ifd)?);
+    (0..count)
+        .map(|index| ifd + 2 + index * 12)
+        .find(|&entry| read_u16(entry) == Some(EXIF_ORIENTATION_TAG))
+        .and_then(|entry| read_u16(entry + 8))
+}
+
+/// Position of the length field of the first JPEG segment before the image
+/// data for which `wanted(marker, position)` holds.
+fn jpeg_segment(bytes: &[u8], wanted: impl Fn(u8, usize) -> bool) -> Option<usize> {
+    let mut pos = 2;
+
+    loop {
+        // Markers may be preceded by any number of 0xFF fill bytes
+        while *bytes.get(pos)? == 0xFF && *bytes.get(pos + 1)? == 0xFF {
+            pos += 1;
+        }
+        if *bytes.get(pos)? != 0xFF {
+            return None;
+        }
+        let marker = *bytes.get(pos + 1)?;
+        pos += 2;
+
+        match marker {
+            // Standalone markers carry no length
+            0x01 | 0xD0..=0xD7 => continue,
+            // End of image or start of scan
+            0xD9 | 0xDA => return None,
+            _ if wanted(marker, pos) => return Some(pos),
+            _ => {
+                let length = usize::from(read_u16_be(bytes, pos)?);
+                pos = pos.checked_add(length)?;
+            }
+        }
+    }
+}
+
+fn read_u16_be(bytes: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(bytes.get(offset..offset + 2)?.try_into().ok()?))
+}
+
+fn read_u32_be(bytes: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(bytes.get(offset..offset + 4)?.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR\x00\x00\x01\x40\x00\x00\x00\xf0\x08\x06\x00\x00\x00";
+    const GIF: &[u8] = b"GIF89a\x20\x03\x58\x02\xf7\x00\x00";
+    // An APP0 segment, then SOF0 with height 0x1E0 and width 0x280
+    const JPEG: &[u8] = b"\xff\xd8\xff\xe0\x00\x04JF\xff\xc0\x00\x11\x08\x01\xe0\x02\x80\x03\x01\x22\x00";
+
+    #[test]
+    fn png_dimensions_from_ihdr() {
+        assert_eq!(dimensions(PNG), Some((320, 240)));
+    }
+
+    #[test]
+    fn gif_dimensions_from_screen_descriptor() {
+        assert_eq!(dimensions(GIF), Some((800, 600)));
+    }
+
+    #[test]
+    fn jpeg_dimensions_from_sof() {
+        assert_eq!(dimensions(JPEG), Some((640, 480)));
+    }
+
+    #[test]
+    fn truncated_headers_give_none() {
+        assert_eq!(dimensions(&PNG[..20]), None);
+        assert_eq!(dimensions(&GIF[..8]), None);
+        assert_eq!(dimensions(&JPEG[..16]), None);
+        assert_eq!(dimensions(b"not an image"), None);
+    }
+
+    // APP1 EXIF segments whose IFD0 holds only an Orientation tag (6 and 8),
+    // followed by the start of scan
+    const EXIF_BIG_ENDIAN: &[u8] = b"\xff\xd8\xff\xe1\x00\x22Exif\0\0MM\0*\0\0\0\x08\0\x01\x01\x12\0\x03\0\0\0\x01\0\x06\0\0\0\0\0\0\xff\xda";
+    const EXIF_LITTLE_ENDIAN: &[u8] = b"\xff\xd8\xff\xe1\x00\x22Exif\0\0II*\0\x08\0\0\0\x01\0\x12\x01\x03\0\x01\0\0\0\x08\0\0\0\0\0\0\0\xff\xda";
+
+    #[test]
+    fn orientation_in_either_byte_order() {
+        assert_eq!(jpeg_orientation(EXIF_BIG_ENDIAN), Some(6));
+        assert_eq!(jpeg_orientation(EXIF_LITTLE_ENDIAN), Some(8));
+    }
+
+    #[test]
+    fn no_orientation_without_exif() {
+        assert_eq!(jpeg_orientation(JPEG), None);
+        assert_eq!(jpeg_orientation(PNG), None);
+    }
+}
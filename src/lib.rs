@@ -1,109 +1,923 @@
 //! ufile-core: Pure file type identification logic for use in CLI and Wasm frontends.
+//!
+//! Without the default `std` feature only `core_detect`, `MagicEntry`,
+//! `dimensions` and `jpeg_orientation` are available, and the crate is `no_std`.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "async")]
+mod async_io;
+#[cfg(feature = "base64")]
+mod base64_input;
+#[cfg(feature = "std")]
+mod category;
+#[cfg(feature = "std")]
+mod compression;
+#[cfg(feature = "std")]
+mod containers;
+pub mod core_detect;
+#[cfg(feature = "std")]
+mod detector;
+#[cfg(feature = "std")]
+mod diff;
+#[cfg(feature = "std")]
+mod executable;
+#[cfg(feature = "std")]
+mod extension_map;
+#[cfg(feature = "std")]
+mod file_type;
+#[cfg(feature = "std")]
+mod font;
+#[cfg(feature = "std")]
+mod format;
+#[cfg(feature = "gitignore")]
+mod gitignore;
+#[cfg(feature = "hashing")]
+mod hashing;
+mod image;
+#[cfg(feature = "std")]
+mod mac_metadata;
+#[cfg(feature = "serde")]
+mod magic_file;
 mod magicnums;
+#[cfg(feature = "mmap")]
+mod mmap;
+#[cfg(feature = "parallel")]
+mod parallel;
+#[cfg(feature = "std")]
+mod partial;
+#[cfg(feature = "std")]
+mod pdf;
+#[cfg(feature = "std")]
+mod polyglot;
+#[cfg(feature = "std")]
+mod registry;
+#[cfg(feature = "serde")]
+mod report;
+#[cfg(feature = "std")]
+mod scan;
+#[cfg(feature = "std")]
+mod shebang;
+#[cfg(feature = "std")]
+mod sqlite;
+#[cfg(feature = "std")]
+mod streams;
+#[cfg(feature = "std")]
+mod summary;
+#[cfg(feature = "std")]
+mod supported;
+#[cfg(feature = "std")]
+mod text;
+#[cfg(feature = "std")]
+mod traversal;
+#[cfg(feature = "std")]
+mod tree;
+#[cfg(feature = "wasm")]
+mod wasm;
+#[cfg(feature = "std")]
+mod xml;
+#[cfg(feature = "async")]
+pub use async_io::{identify_file_from_path_async, identify_from_async_reader};
+#[cfg(feature = "base64")]
+pub use base64_input::{Base64Error, identify_from_base64};
+#[cfg(feature = "std")]
+pub use category::Category;
+#[cfg(feature = "std")]
+pub use compression::{Compression, compression, compression_ratio, gzip_original_name};
+#[cfg(feature = "std")]
+pub use containers::{MAX_ARCHIVE_ENTRIES, cfb_subtype, list_archive_entries, refine_zip};
+#[cfg(feature = "std")]
+pub use detector::{Detector, DetectorChain, InferDetector, MagicDetector, TextDetector};
+#[cfg(feature = "std")]
+pub use diff::{ScanDiff, diff_scans};
+#[cfg(feature = "std")]
+pub use executable::{ExecutableFormat, ExecutableInfo, executable_info};
+#[cfg(feature = "std")]
+pub use extension_map::{ExtensionEntry, describe_extension, extension_for_mime, get_extension_map};
+#[cfg(feature = "std")]
+pub use file_type::FileType;
+#[cfg(feature = "std")]
+pub use font::{FontFormat, FontInfo, font_info};
+#[cfg(feature = "std")]
+pub use format::{format_size, format_size_si};
+#[cfg(feature = "gitignore")]
+pub use gitignore::identify_recursive_gitignore;
+#[cfg(feature = "hashing")]
+pub use hashing::{find_duplicates, identify_file_with_hash};
+pub use image::{dimensions, jpeg_orientation};
+#[cfg(feature = "std")]
+pub use mac_metadata::is_mac_metadata;
+#[cfg(feature = "serde")]
+pub use magic_file::{MagicParseError, load_magic_from_str};
+pub use magicnums::MagicEntry;
+#[cfg(feature = "std")]
 pub use magicnums::get_magic_numbers;
+#[cfg(feature = "mmap")]
+pub use mmap::identify_file_mmap;
+#[cfg(feature = "parallel")]
+pub use parallel::{identify_many_bytes_parallel, identify_recursive_parallel};
+#[cfg(feature = "std")]
+pub use partial::{PartialResult, identify_partial};
+#[cfg(feature = "std")]
+pub use pdf::{PdfInfo, pdf_info};
+#[cfg(feature = "std")]
+pub use polyglot::detect_polyglot;
+#[cfg(feature = "std")]
+pub use registry::{MagicConflict, MagicRegistry, MergeConflict, MergePolicy};
+#[cfg(feature = "serde")]
+pub use report::to_json_report;
+#[cfg(feature = "std")]
+pub use scan::{ScanCache, ScanConfig, ScanStats};
+#[cfg(feature = "std")]
+pub use shebang::detect_shebang;
+#[cfg(feature = "std")]
+pub use sqlite::{SqliteInfo, sqlite_info};
+#[cfg(feature = "std")]
+pub use streams::{MAX_STREAMS, identify_streams};
+#[cfg(feature = "std")]
+pub use summary::{ScanSummary, summarize};
+#[cfg(feature = "std")]
+pub use supported::{FormatSpec, supported_formats};
+#[cfg(feature = "std")]
+pub use text::{Bom, LineEnding, TextKind, classify_text, detect_bom, line_ending_style};
+#[cfg(feature = "std")]
+pub use traversal::{
+    TraversalOptions, identify_recursive_collect_errors, identify_recursive_filtered, identify_recursive_grouped,
+    identify_recursive_iter, identify_recursive_limited, identify_recursive_relative, identify_recursive_until_error,
+    identify_recursive_with_options, identify_recursive_with_progress,
+};
+#[cfg(feature = "std")]
+pub use tree::{DirNode, TreeEntry, identify_tree};
+#[cfg(feature = "std")]
+pub use xml::xml_dialect;
 
+#[cfg(feature = "std")]
+use std::collections::BTreeSet;
+#[cfg(feature = "std")]
 use std::fs;
-use std::io;
+#[cfg(feature = "std")]
+use std::io::{self, Read, Seek, SeekFrom};
+#[cfg(feature = "std")]
 use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+#[cfg(feature = "std")]
+use std::time::SystemTime;
+
+#[cfg(feature = "std")]
+use magicnums::MAGIC_NUMBERS;
 
 /// Structured information about a detected file type.
+#[cfg(feature = "std")]
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FileInfo {
-    /// The path to the file
+    /// The path to the file, serialized as a (lossy UTF-8) string
+    #[cfg_attr(feature = "serde", serde(serialize_with = "report::serialize_path_lossy"))]
     pub path: PathBuf,
     /// A human-readable description of the file type
     pub description: String,
+    /// The detected type, for matching without comparing descriptions
+    pub file_type: FileType,
+    /// The MIME type of the file, if known
+    pub mime_type: Option<String>,
     /// Whether this is a directory
     pub is_directory: bool,
-    /// File size in bytes (None for directories)
+    /// File size in bytes (None for directories and unfollowed symbolic links)
     pub size: Option<u64>,
+    /// How reliable the detected type is
+    pub confidence: Confidence,
+    /// Last modification time, if read from the file system and supported
+    /// by the platform (None for byte-based identification)
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, serialize_with = "report::serialize_time", deserialize_with = "report::deserialize_time")
+    )]
+    pub modified: Option<SystemTime>,
+    /// Creation time, if read from the file system and supported by the
+    /// platform (None for byte-based identification)
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, serialize_with = "report::serialize_time", deserialize_with = "report::deserialize_time")
+    )]
+    pub created: Option<SystemTime>,
+    /// Whether any executable permission bit is set, for files read from the
+    /// file system on Unix (None elsewhere, and for directories and special files)
+    pub is_executable: Option<bool>,
+    /// Which detection step produced the result
+    pub source: DetectionSource,
+}
+
+/// The description of files whose type could not be determined.
+#[cfg(feature = "std")]
+pub const UNKNOWN_DESCRIPTION: &str = "Unknown file type";
+
+/// The description of empty files and empty input.
+#[cfg(feature = "std")]
+pub const EMPTY_DESCRIPTION: &str = "Empty file";
+
+#[cfg(feature = "std")]
+impl FileInfo {
+    /// Whether the file's type could not be determined. Checks `file_type`
+    /// rather than `description`, so this holds even after the description has
+    /// been rewritten, e.g. by `ScanConfig::description_mapper`.
+    pub fn is_unknown(&self) -> bool {
+        matches!(&self.file_type, FileType::Unknown(description) if description == UNKNOWN_DESCRIPTION)
+    }
+
+    /// Coarse category of the detected type, based on the MIME type when
+    /// available and on the description otherwise. Directories are always
+    /// `Category::Directory`.
+    pub fn category(&self) -> Category {
+        if self.is_directory {
+            return Category::Directory;
+        }
+        category::categorize(self.mime_type.as_deref(), &self.description)
+    }
+
+    /// The size formatted with `format_size`, or "-" when there is no size,
+    /// as for directories.
+    pub fn size_display(&self) -> String {
+        self.size.map_or_else(|| "-".to_string(), format_size)
+    }
+}
+
+/// How reliable a detected file type is.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Confidence {
+    /// Matched a signature in the custom magic number table
+    Exact,
+    /// Matched by the `infer` fallback
+    Probable,
+    /// Inferred from the file name or not recognized at all
+    Guess,
+}
+
+/// Which detection step produced a result.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DetectionSource {
+    /// A signature in the custom magic number table or a registry
+    CustomMagic,
+    /// The `infer` fallback
+    Infer,
+    /// The file name's extension
+    Extension,
+    /// Text classification of the content
+    Text,
+    /// The file system entry type, for directories, symbolic links and
+    /// special files, whose content isn't read
+    Special,
+    /// The input was empty
+    Empty,
+    /// Nothing recognized the content
+    Unknown,
+}
+
+/// Details of how a file type was detected, for debugging misdetections.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetectionDetail {
+    /// The matched magic bytes (empty unless `source` is `CustomMagic`)
+    pub magic: Vec<u8>,
+    /// Offset of the matched magic bytes within the input, counted back from
+    /// the end for entries flagged `from_end`, and where the magic was found
+    /// for entries with a search window
+    pub offset: usize,
+    /// Number of signature bytes that matched, for ranking results; longer
+    /// signatures are more specific. 0 unless `source` is `CustomMagic`
+    pub matched_bytes: usize,
+    /// Which detection step produced the result
+    pub source: DetectionSource,
+}
+
+#[cfg(feature = "std")]
+impl DetectionDetail {
+    /// Detail for a source that doesn't match a specific signature.
+    fn without_signature(source: DetectionSource) -> Self {
+        Self {
+            magic: Vec::new(),
+            offset: 0,
+            matched_bytes: 0,
+            source,
+        }
+    }
+}
+
+/// A disagreement between a file's extension and its detected content.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtensionMismatch {
+    /// The file extension, without the leading dot
+    pub extension: String,
+    /// The MIME type the extension claims
+    pub expected_mime: String,
+    /// The MIME type detected from the content
+    pub detected_mime: String,
+    /// A human-readable description of the detected content
+    pub detected_description: String,
 }
 
 /// Error types for file processing operations.
+#[cfg(feature = "std")]
 #[derive(Debug, thiserror::Error)]
 pub enum FileProcessingError {
     #[error("IO error: {0}")]
     Io(#[from] io::Error),
     #[error("Path does not exist: {0}")]
     PathNotFound(PathBuf),
+    #[error("Not a directory: {0}")]
+    NotADirectory(PathBuf),
+    #[error("Not a regular file: {0}")]
+    NotAFile(PathBuf),
+    #[error("File too large: {path} is {size} bytes, over the limit of {limit}")]
+    FileTooLarge { path: PathBuf, size: u64, limit: u64 },
     #[error("Directory traversal error: {0}")]
     WalkDir(#[from] walkdir::Error),
+    #[cfg(feature = "gitignore")]
+    #[error("Ignore-aware traversal error: {0}")]
+    Ignore(#[from] ignore::Error),
 }
 
-/// Identify the file type from a byte slice.
+/// Identify the file type from a byte slice with the default `DetectorChain`:
+/// the built-in magic numbers, then `infer`, then text classification.
 /// Returns Some(FileInfo) if recognized, or None otherwise.
+#[cfg(feature = "std")]
 pub fn identify_from_bytes(bytes: &[u8]) -> Option<FileInfo> {
-    for entry in get_magic_numbers() {
-        if bytes.len() >= entry.offset + entry.magic.len()
-            && &bytes[entry.offset..entry.offset + entry.magic.len()] == entry.magic
-        {
-            return Some(FileInfo {
-                path: PathBuf::new(),
-                description: entry.description.to_string(),
-                is_directory: false,
-                size: Some(bytes.len() as u64),
-            });
-        }
+    static DEFAULT_CHAIN: OnceLock<DetectorChain> = OnceLock::new();
+    DEFAULT_CHAIN.get_or_init(DetectorChain::default).detect(bytes)
+}
+
+/// The description of the built-in magic entry matching `bytes`, without
+/// allocating. For tight loops where a FileInfo per call is too costly.
+/// Only the built-in table is consulted: there is no `infer` fallback or text
+/// classification, and matches are not refined by their content, so ZIP
+/// containers (e.g. DOCX), compound files (e.g. DOC), scripts identified by
+/// their shebang, and XML dialects such as SVG or XHTML keep their generic
+/// description. For matched entries other than those, the description is the
+/// same as from `identify_from_bytes`.
+#[cfg(feature = "std")]
+pub fn identify_kind(bytes: &[u8]) -> Option<&'static str> {
+    core_detect::identify(bytes).map(|(description, _)| description)
+}
+
+/// Identify the file type from a byte slice using only the built-in magic
+/// numbers. Unlike `identify_from_bytes`, neither the `infer` fallback nor text
+/// classification is consulted, so every result comes from the curated table
+/// with `Confidence::Exact`, and anything else is None. Empty input is still
+/// reported as "Empty file".
+#[cfg(feature = "std")]
+pub fn identify_from_bytes_strict(bytes: &[u8]) -> Option<FileInfo> {
+    DetectorChain::new().with(MagicDetector::new()).detect(bytes)
+}
+
+/// Identify the file type from a byte slice and report how it was detected,
+/// including the matched signature and its offset.
+#[cfg(feature = "std")]
+pub fn identify_from_bytes_detailed(bytes: &[u8]) -> Option<(FileInfo, DetectionDetail)> {
+    MagicRegistry::default().identify_detailed(bytes)
+}
+
+/// Identify the file type from a byte slice like `identify_from_bytes`, also
+/// returning a hex preview of its first `preview_len` bytes (fewer if the input
+/// is shorter), for reporting why detection failed. Bytes are lowercase hex
+/// pairs separated by spaces, e.g. "89 50 4e 47".
+#[cfg(feature = "std")]
+pub fn identify_with_preview(bytes: &[u8], preview_len: usize) -> (Option<FileInfo>, String) {
+    let preview = bytes[..bytes.len().min(preview_len)]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    (identify_from_bytes(bytes), preview)
+}
+
+/// Every type a byte slice could be according to the built-in magic numbers and
+/// the `infer` fallback, most specific first. See
+/// `MagicRegistry::identify_all_matches` for the ordering.
+#[cfg(feature = "std")]
+pub fn identify_all_matches(bytes: &[u8]) -> Vec<FileInfo> {
+    MagicRegistry::default().identify_all_matches(bytes)
+}
+
+/// Minimum number of header bytes read per file, so the `infer` fallback
+/// has enough data for formats it recognizes beyond the first few bytes.
+#[cfg(feature = "std")]
+const MIN_HEADER_READ_LEN: usize = 8 * 1024;
+
+/// Number of leading bytes read to identify a file: the largest
+/// `offset + magic.len()` (or search window end) in the magic number table, but
+/// never less than 8 KiB. Entries matched from the end of the input are not counted.
+#[cfg(feature = "std")]
+pub fn header_read_len() -> usize {
+    MAGIC_NUMBERS
+        .iter()
+        .filter(|entry| !entry.from_end || entry.search_window.is_some())
+        .map(MagicEntry::head_len)
+        .max()
+        .unwrap_or(0)
+        .max(MIN_HEADER_READ_LEN)
+}
+
+/// Number of trailing bytes read to match the entries flagged `from_end`.
+#[cfg(feature = "std")]
+fn tail_read_len() -> usize {
+    MAGIC_NUMBERS
+        .iter()
+        .filter(|entry| entry.from_end && entry.search_window.is_none())
+        .map(|entry| entry.offset)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Read at most `limit` bytes from the start of `reader`.
+#[cfg(feature = "std")]
+fn read_header<R: Read>(reader: R, limit: usize) -> io::Result<Vec<u8>> {
+    let mut header = Vec::with_capacity(limit);
+    reader.take(limit as u64).read_to_end(&mut header)?;
+    Ok(header)
+}
+
+/// Read at most `limit` leading bytes from `reader`, and if the input continues
+/// past them, its last `tail_read_len()` bytes as well.
+#[cfg(feature = "std")]
+fn read_head_and_tail<R: Read + Seek>(reader: &mut R, limit: usize) -> io::Result<(Vec<u8>, Option<Vec<u8>>)> {
+    let head = read_header(&mut *reader, limit)?;
+    let tail_len = tail_read_len() as u64;
+    if head.len() < limit || tail_len == 0 {
+        return Ok((head, None));
+    }
+
+    let end = reader.seek(SeekFrom::End(0))?;
+    reader.seek(SeekFrom::Start(end.saturating_sub(tail_len)))?;
+    let tail = read_header(reader, tail_len as usize)?;
+    Ok((head, Some(tail)))
+}
+
+/// Identify the file type from a reader without loading the whole input.
+/// Consumes at most `header_read_len()` bytes from the reader, stopping early at EOF.
+/// Trailer signatures are only matched if the input ends within those bytes; use
+/// `identify_from_seekable` when the reader can seek.
+/// Since the total length is unknown, the returned FileInfo has `size: None`.
+#[cfg(feature = "std")]
+pub fn identify_from_reader<R: Read>(reader: &mut R) -> io::Result<Option<FileInfo>> {
+    let header = read_header(reader, header_read_len())?;
+    Ok(identify_from_bytes(&header).map(|info| FileInfo { size: None, ..info }))
+}
+
+/// Identify the file type from a seekable reader such as an open file.
+/// Like `identify_from_reader`, but also seeks to the end of the input to match
+/// signatures stored in a trailer. The returned FileInfo has `size: None`.
+#[cfg(feature = "std")]
+pub fn identify_from_seekable<R: Read + Seek>(reader: &mut R) -> io::Result<Option<FileInfo>> {
+    let (head, tail) = read_head_and_tail(reader, header_read_len())?;
+    let tail = tail.as_deref().unwrap_or(&head);
+    Ok(MagicRegistry::default()
+        .identify_parts(&head, tail)
+        .map(|(info, _)| FileInfo { size: None, ..info }))
+}
+
+/// Identify an already open file through its handle, without opening it again.
+/// The header is read from the start of the file whatever the current position,
+/// and the cursor is rewound to the start afterwards. The size comes from the
+/// handle's metadata. Since the handle carries no path, the returned FileInfo
+/// has an empty `path`.
+#[cfg(feature = "std")]
+pub fn identify_from_file(file: &mut fs::File) -> Result<FileInfo, FileProcessingError> {
+    let metadata = file.metadata()?;
+    let path = Path::new("");
+
+    if metadata.is_dir() {
+        return Ok(with_metadata(create_directory_info(path), &metadata));
     }
-    // Fallback to infer if no custom magic matched
-    if let Some(kind) = infer::get(bytes) {
-        return Some(FileInfo {
-            path: PathBuf::new(),
-            description: kind.mime_type().to_string(),
+    if let Some(info) = create_special_info(path, metadata.file_type()) {
+        return Ok(with_metadata(info, &metadata));
+    }
+
+    file.seek(SeekFrom::Start(0))?;
+    let (head, tail) = read_head_and_tail(&mut *file, header_read_len())?;
+    file.seek(SeekFrom::Start(0))?;
+    let info = identify_header(path, &head, tail.as_deref().unwrap_or(&head), metadata.len());
+    Ok(with_metadata(info, &metadata))
+}
+
+/// Identify the file type from a byte slice, falling back to the path's extension.
+/// Content-based detection wins; the extension is only consulted when the bytes
+/// are not recognized, or are only recognized as generic text and the extension
+/// names a more specific text format such as CSV or JSON. Such results are
+/// marked "(from extension)".
+#[cfg(feature = "std")]
+pub fn identify_with_extension(path: &Path, bytes: &[u8]) -> FileInfo {
+    match identify_from_bytes(bytes) {
+        Some(info) if !extension_map::lookup(path).is_some_and(|entry| refines_text(&info, entry)) => FileInfo {
+            path: path.to_path_buf(),
+            ..info
+        },
+        _ => extension_info(path, bytes.len() as u64),
+    }
+}
+
+/// Guess a file's type from its path's extension alone, without opening it.
+/// Directories, symbolic links whose target is missing and special files are
+/// reported as by `identify_multiple`; for other files the extension map
+/// decides, with results marked "(from extension)" as in
+/// `identify_with_extension`. Size, timestamps and permissions come from the
+/// file system metadata, which is the only thing read.
+#[cfg(feature = "std")]
+pub fn identify_metadata_only(path: &Path) -> Result<FileInfo, FileProcessingError> {
+    let metadata = match fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {
+            return broken_symlink_info(path).ok_or_else(|| FileProcessingError::PathNotFound(path.to_path_buf()));
+        }
+        Err(error) => return Err(error.into()),
+    };
+
+    let info = if metadata.is_dir() {
+        create_directory_info(path)
+    } else if let Some(info) = create_special_info(path, metadata.file_type()) {
+        info
+    } else {
+        extension_info(path, metadata.len())
+    };
+
+    Ok(with_metadata(info, &metadata))
+}
+
+/// The FileInfo for a file of `size` bytes whose type is taken from its
+/// path's extension, or an unknown one if the extension isn't recognized.
+#[cfg(feature = "std")]
+fn extension_info(path: &Path, size: u64) -> FileInfo {
+    match extension_map::lookup(path) {
+        Some(entry) => FileInfo {
+            path: path.to_path_buf(),
+            description: format!("{} (from extension)", entry.description),
+            file_type: FileType::detected(entry.mime, entry.description),
+            mime_type: entry.mime.map(str::to_string),
             is_directory: false,
-            size: Some(bytes.len() as u64),
-        });
+            size: Some(size),
+            confidence: Confidence::Guess,
+            modified: None,
+            created: None,
+            is_executable: None,
+            source: DetectionSource::Extension,
+        },
+        None => create_unknown_info(path, size),
+    }
+}
+
+/// Identify the file type from a byte slice, using `filename`'s extension to
+/// narrow down generic containers: a ZIP named `book.xlsx` is reported as an
+/// XLSX spreadsheet, and text named `data.csv` as CSV data. The extension is
+/// only used when its type is built on the detected container or is a text
+/// format refining generic text, so it never overrides a more specific content
+/// match, and it is never used on its own: unrecognized bytes still give None.
+/// The result's `path` is set to `filename`.
+#[cfg(feature = "std")]
+pub fn identify_from_bytes_with_hint(bytes: &[u8], filename: &str) -> Option<FileInfo> {
+    let info = FileInfo {
+        path: PathBuf::from(filename),
+        ..identify_from_bytes(bytes)?
+    };
+
+    let hinted = extension_map::lookup(Path::new(filename))
+        .filter(|entry| {
+            refines_text(&info, entry)
+                || entry
+                    .mime
+                    .zip(info.mime_type.as_deref())
+                    .is_some_and(|(mime, detected)| extension_map::is_contained_in(mime, detected))
+        })
+        .and_then(|entry| Some((entry.description, entry.mime?)));
+
+    Some(match hinted {
+        Some((description, mime)) => FileInfo {
+            description: description.to_string(),
+            file_type: FileType::detected(Some(mime), description),
+            mime_type: Some(mime.to_string()),
+            confidence: Confidence::Probable,
+            source: DetectionSource::Extension,
+            ..info
+        },
+        None => info,
+    })
+}
+
+/// Whether the extension map `entry` names a text format more specific than
+/// `info`, which was detected as generic text. Content detection can't tell
+/// e.g. CSV or JSON from other text, but it can tell the text's encoding, so
+/// plain text extensions such as `.txt` don't count.
+#[cfg(feature = "std")]
+fn refines_text(info: &FileInfo, entry: &ExtensionEntry) -> bool {
+    info.file_type == FileType::PlainText
+        && entry.mime != Some("text/plain")
+        && category::categorize(entry.mime, entry.description) == Category::Text
+}
+
+/// Compare the type claimed by a path's extension with the type detected from its content.
+/// Returns None when both agree, or when either the extension or the content is not
+/// recognized. A generic container (e.g. ZIP) is considered to agree with formats
+/// built on it (e.g. DOCX, JAR).
+#[cfg(feature = "std")]
+pub fn check_extension_mismatch(path: &Path, bytes: &[u8]) -> Option<ExtensionMismatch> {
+    let expected_mime = extension_map::lookup(path)?.mime?;
+    let detected = identify_from_bytes(bytes)?;
+    let detected_mime = detected.mime_type?;
+
+    if detected_mime == expected_mime || extension_map::is_contained_in(expected_mime, &detected_mime) {
+        return None;
     }
-    None
+
+    Some(ExtensionMismatch {
+        extension: path.extension()?.to_string_lossy().into_owned(),
+        expected_mime: expected_mime.to_string(),
+        detected_mime,
+        detected_description: detected.description,
+    })
+}
+
+/// Build a human-readable description for a type detected by `infer`,
+/// e.g. "WEBP image" or "EPUB e-book".
+#[cfg(feature = "std")]
+fn describe_infer_type(kind: &infer::Type) -> String {
+    let noun = match kind.matcher_type() {
+        infer::MatcherType::App => "application",
+        infer::MatcherType::Archive => "archive",
+        infer::MatcherType::Audio => "audio",
+        infer::MatcherType::Book => "e-book",
+        infer::MatcherType::Doc => "document",
+        infer::MatcherType::Font => "font",
+        infer::MatcherType::Image => "image",
+        infer::MatcherType::Text => "text",
+        infer::MatcherType::Video => "video",
+        infer::MatcherType::Custom => "data",
+    };
+    format!("{} {}", kind.extension().to_uppercase(), noun)
 }
 
 /// Create a FileInfo for a directory.
 /// Helper function for multi-file operations.
+#[cfg(feature = "std")]
 fn create_directory_info<P: AsRef<Path>>(path: P) -> FileInfo {
     FileInfo {
         path: path.as_ref().to_path_buf(),
         description: "Directory".to_string(),
+        file_type: FileType::Directory,
+        mime_type: None,
         is_directory: true,
         size: None,
+        confidence: Confidence::Exact,
+        modified: None,
+        created: None,
+        is_executable: None,
+        source: DetectionSource::Special,
     }
 }
 
-/// Create a FileInfo for a file by reading it and identifying its type.
+/// Create a FileInfo for a symbolic link that is not being followed.
 /// Helper function for multi-file operations.
-fn identify_file_from_path<P: AsRef<Path>>(path: P) -> Result<FileInfo, FileProcessingError> {
-    let path = path.as_ref();
-    let metadata = fs::metadata(path)?;
-    
-    if metadata.is_dir() {
-        return Ok(create_directory_info(path));
+#[cfg(feature = "std")]
+fn create_symlink_info<P: AsRef<Path>>(path: P) -> FileInfo {
+    FileInfo {
+        path: path.as_ref().to_path_buf(),
+        description: "Symbolic link".to_string(),
+        file_type: FileType::Symlink,
+        mime_type: None,
+        is_directory: false,
+        size: None,
+        confidence: Confidence::Exact,
+        modified: None,
+        created: None,
+        is_executable: None,
+        source: DetectionSource::Special,
     }
+}
 
-    let bytes = fs::read(path)?;
-    let description = if let Some(info) = identify_from_bytes(&bytes) {
-        info.description
+/// Create a FileInfo for a symbolic link whose target doesn't exist.
+/// Helper function for multi-file operations.
+#[cfg(feature = "std")]
+fn create_broken_symlink_info<P: AsRef<Path>>(path: P) -> FileInfo {
+    FileInfo {
+        description: "Broken symbolic link".to_string(),
+        file_type: FileType::BrokenSymlink,
+        ..create_symlink_info(path)
+    }
+}
+
+/// Create a FileInfo for a Windows reparse point that isn't a symbolic link,
+/// given its own (not its target's) metadata.
+/// Returns None for other entries, and always on other platforms.
+#[cfg(feature = "std")]
+fn create_reparse_point_info(path: &Path, link: &fs::Metadata) -> Option<FileInfo> {
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+
+        const FILE_ATTRIBUTE_REPARSE_POINT: u32 = 0x400;
+        (link.file_attributes() & FILE_ATTRIBUTE_REPARSE_POINT != 0).then(|| FileInfo {
+            description: "Reparse point".to_string(),
+            file_type: FileType::ReparsePoint,
+            ..create_symlink_info(path)
+        })
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = (path, link);
+        None
+    }
+}
+
+/// The FileInfo for `path` if it is a broken symbolic link or a reparse point
+/// that can't be resolved, for when reading its target's metadata has failed.
+#[cfg(feature = "std")]
+fn broken_symlink_info(path: &Path) -> Option<FileInfo> {
+    unresolved_link_info(path, &fs::symlink_metadata(path).ok()?)
+}
+
+/// The FileInfo for `path` as a link that can't be resolved, given its own metadata.
+#[cfg(feature = "std")]
+fn unresolved_link_info(path: &Path, link: &fs::Metadata) -> Option<FileInfo> {
+    let info = if link.file_type().is_symlink() {
+        create_broken_symlink_info(path)
     } else {
-        "Unknown file type".to_string()
+        create_reparse_point_info(path, link)?
     };
-    
-    Ok(FileInfo {
-        path: path.to_path_buf(),
-        description,
+    Some(with_metadata(info, link))
+}
+
+/// Create a FileInfo for a named pipe, socket or device file without reading
+/// it, since reads from these may block or never end.
+/// Returns None for regular files, directories and symbolic links.
+#[cfg(feature = "std")]
+fn create_special_info(path: &Path, file_type: fs::FileType) -> Option<FileInfo> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileTypeExt;
+
+        let (description, file_type) = if file_type.is_fifo() {
+            ("Named pipe (FIFO)", FileType::NamedPipe)
+        } else if file_type.is_socket() {
+            ("Socket", FileType::Socket)
+        } else if file_type.is_block_device() {
+            ("Block device", FileType::BlockDevice)
+        } else if file_type.is_char_device() {
+            ("Character device", FileType::CharDevice)
+        } else {
+            return None;
+        };
+
+        Some(FileInfo {
+            path: path.to_path_buf(),
+            description: description.to_string(),
+            file_type,
+            mime_type: None,
+            is_directory: false,
+            size: None,
+            confidence: Confidence::Exact,
+            modified: None,
+            created: None,
+            is_executable: None,
+            source: DetectionSource::Special,
+        })
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = (path, file_type);
+        None
+    }
+}
+
+/// Create a FileInfo for a file whose type could not be determined.
+/// Helper function for multi-file operations.
+#[cfg(feature = "std")]
+fn create_unknown_info<P: AsRef<Path>>(path: P, size: u64) -> FileInfo {
+    FileInfo {
+        path: path.as_ref().to_path_buf(),
+        description: UNKNOWN_DESCRIPTION.to_string(),
+        file_type: FileType::Unknown(UNKNOWN_DESCRIPTION.to_string()),
+        mime_type: None,
         is_directory: false,
-        size: Some(metadata.len()),
-    })
+        size: Some(size),
+        confidence: Confidence::Guess,
+        modified: None,
+        created: None,
+        is_executable: None,
+        source: DetectionSource::Unknown,
+    }
+}
+
+/// Create a FileInfo for a file by reading its header and identifying its type.
+/// Only the first `header_read_len()` bytes are read; `size` comes from metadata.
+/// Helper function for multi-file operations.
+#[cfg(feature = "std")]
+fn identify_file_from_path<P: AsRef<Path>>(path: P) -> Result<FileInfo, FileProcessingError> {
+    identify_file_with_limit(path.as_ref(), header_read_len())
+}
+
+/// Identify a single file or directory, reading at most `limit` bytes of the file.
+#[cfg(feature = "std")]
+fn identify_file_with_limit(path: &Path, limit: usize) -> Result<FileInfo, FileProcessingError> {
+    identify_file_counted(path, limit, None).map(|(info, _)| info)
+}
+
+/// Like `identify_file_with_limit`, but also returns how many bytes were read.
+/// With `categories`, the `infer` fallback only reports types in those
+/// categories; see `identify_header_restricted`.
+#[cfg(feature = "std")]
+fn identify_file_counted(
+    path: &Path,
+    limit: usize,
+    categories: Option<&BTreeSet<Category>>,
+) -> Result<(FileInfo, u64), FileProcessingError> {
+    // Paths beyond MAX_PATH need no `\\?\` prefix: std converts them to verbatim form on Windows
+    let metadata = match fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(error) => return broken_symlink_info(path).map(|info| (info, 0)).ok_or(error.into()),
+    };
+    
+    let (info, bytes_read) = if metadata.is_dir() {
+        (create_directory_info(path), 0)
+    } else if let Some(info) = create_special_info(path, metadata.file_type()) {
+        (info, 0)
+    } else {
+        let (head, tail) = read_head_and_tail(&mut fs::File::open(path)?, limit)?;
+        let bytes_read = head.len() + tail.as_ref().map_or(0, Vec::len);
+        let info = identify_header_restricted(path, &head, tail.as_deref().unwrap_or(&head), metadata.len(), categories);
+        (info, bytes_read as u64)
+    };
+
+    Ok((with_metadata(info, &metadata), bytes_read))
+}
+
+/// Fill in the timestamps and permissions of `info` from the file system
+/// metadata of its file.
+#[cfg(feature = "std")]
+fn with_metadata(info: FileInfo, metadata: &fs::Metadata) -> FileInfo {
+    FileInfo {
+        modified: metadata.modified().ok(),
+        created: metadata.created().ok(),
+        is_executable: is_executable(metadata),
+        ..info
+    }
+}
+
+/// Whether a regular file has any executable permission bit set, on Unix.
+#[cfg(feature = "std")]
+fn is_executable(metadata: &fs::Metadata) -> Option<bool> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        metadata.is_file().then(|| metadata.permissions().mode() & 0o111 != 0)
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = metadata;
+        None
+    }
+}
+
+/// Build the FileInfo for a file of `size` bytes at `path` from its leading
+/// and trailing bytes. Unrecognized files named `.DS_Store` are labeled as
+/// Finder metadata by their name.
+#[cfg(feature = "std")]
+fn identify_header(path: &Path, head: &[u8], tail: &[u8], size: u64) -> FileInfo {
+    identify_header_restricted(path, head, tail, size, None)
+}
+
+/// Like `identify_header`, but with `categories` the `infer` fallback only
+/// reports types in those categories, so its broad matchers can't label a
+/// file as something outside them.
+#[cfg(feature = "std")]
+fn identify_header_restricted(path: &Path, head: &[u8], tail: &[u8], size: u64, categories: Option<&BTreeSet<Category>>) -> FileInfo {
+    match MagicRegistry::default().identify_parts_restricted(head, tail, categories).map(|(info, _)| info) {
+        Some(info) => FileInfo {
+            path: path.to_path_buf(),
+            size: Some(size),
+            ..info
+        },
+        None => mac_metadata::ds_store_info(path, size).unwrap_or_else(|| create_unknown_info(path, size)),
+    }
 }
 
 /// Process multiple files and/or directories.
-/// Returns a vector of FileInfo for all processed items.
+/// Returns a vector of FileInfo for all processed items. Symbolic links whose
+/// target doesn't exist are reported as "Broken symbolic link"; paths that
+/// don't exist at all fail with `PathNotFound`.
+#[cfg(feature = "std")]
 pub fn identify_multiple<P: AsRef<Path>>(paths: &[P]) -> Result<Vec<FileInfo>, FileProcessingError> {
     let mut results = Vec::new();
     
     for path in paths {
         let path = path.as_ref();
         
-        if !path.exists() {
+        if path.symlink_metadata().is_err() {
             return Err(FileProcessingError::PathNotFound(path.to_path_buf()));
         }
         
@@ -114,37 +928,104 @@ pub fn identify_multiple<P: AsRef<Path>>(paths: &[P]) -> Result<Vec<FileInfo>, F
     Ok(results)
 }
 
-/// Recursively process a directory and all its contents.
-/// Returns a vector of FileInfo for all files and subdirectories found.
-pub fn identify_recursive<P: AsRef<Path>>(path: P) -> Result<Vec<FileInfo>, FileProcessingError> {
+/// Identify a single file or directory, refusing files larger than `max_bytes`.
+/// The size is checked from metadata before anything is read, and an oversized
+/// file fails with `FileTooLarge`. Directories are never refused.
+#[cfg(feature = "std")]
+pub fn identify_file_capped(path: &Path, max_bytes: u64) -> Result<FileInfo, FileProcessingError> {
+    if path.symlink_metadata().is_err() {
+        return Err(FileProcessingError::PathNotFound(path.to_path_buf()));
+    }
+
+    if let Ok(metadata) = fs::metadata(path)
+        && metadata.is_file()
+        && metadata.len() > max_bytes
+    {
+        return Err(FileProcessingError::FileTooLarge {
+            path: path.to_path_buf(),
+            size: metadata.len(),
+            limit: max_bytes,
+        });
+    }
+
+    identify_file_from_path(path)
+}
+
+/// Process multiple files and/or directories without aborting on failures.
+/// Returns one result per input path, in the same order as `paths`.
+#[cfg(feature = "std")]
+pub fn identify_multiple_lenient<P: AsRef<Path>>(paths: &[P]) -> Vec<Result<FileInfo, FileProcessingError>> {
+    paths
+        .iter()
+        .map(|path| {
+            let path = path.as_ref();
+
+            if path.symlink_metadata().is_err() {
+                return Err(FileProcessingError::PathNotFound(path.to_path_buf()));
+            }
+
+            identify_file_from_path(path)
+        })
+        .collect()
+}
+
+/// Process the immediate children of a directory, without descending into
+/// subdirectories or reporting the directory itself. Symbolic links are reported
+/// as "Symbolic link". Results are sorted by path.
+#[cfg(feature = "std")]
+pub fn identify_dir_children<P: AsRef<Path>>(path: P) -> Result<Vec<FileInfo>, FileProcessingError> {
     let path = path.as_ref();
-    
+
     if !path.exists() {
         return Err(FileProcessingError::PathNotFound(path.to_path_buf()));
     }
-    
+    if !path.is_dir() {
+        return Err(FileProcessingError::NotADirectory(path.to_path_buf()));
+    }
+
     let mut results = Vec::new();
-    
-    for entry in WalkDir::new(path) {
+    for entry in fs::read_dir(path)? {
         let entry = entry?;
-        let entry_path = entry.path();
-        
-        let file_info = identify_file_from_path(entry_path)?;
+        let file_info = if entry.file_type()?.is_symlink() {
+            create_symlink_info(entry.path())
+        } else {
+            identify_file_from_path(entry.path())?
+        };
         results.push(file_info);
     }
-    
+
+    results.sort_by(|a, b| a.path.cmp(&b.path));
     Ok(results)
 }
 
+/// Recursively process a directory and all its contents.
+/// Returns a vector of FileInfo for all files and subdirectories found.
+#[cfg(feature = "std")]
+pub fn identify_recursive<P: AsRef<Path>>(path: P) -> Result<Vec<FileInfo>, FileProcessingError> {
+    identify_recursive_with_depth(path, usize::MAX)
+}
+
+/// Recursively process a directory, descending at most `max_depth` levels.
+/// Depth 0 is the root itself and depth 1 adds its immediate children.
+#[cfg(feature = "std")]
+pub fn identify_recursive_with_depth<P: AsRef<Path>>(path: P, max_depth: usize) -> Result<Vec<FileInfo>, FileProcessingError> {
+    let options = TraversalOptions {
+        max_depth,
+        ..TraversalOptions::default()
+    };
+    identify_recursive_with_options(path, &options)
+}
+
 /// Process multiple paths, recursively walking directories.
 /// Returns a vector of FileInfo for all processed items.
+#[cfg(feature = "std")]
 pub fn identify_multiple_recursive<P: AsRef<Path>>(paths: &[P]) -> Result<Vec<FileInfo>, FileProcessingError> {
     let mut results = Vec::new();
     
     for path in paths {
         let path = path.as_ref();
         
-        if !path.exists() {
+        if path.symlink_metadata().is_err() {
             return Err(FileProcessingError::PathNotFound(path.to_path_buf()));
         }
         
@@ -159,17 +1040,83 @@ pub fn identify_multiple_recursive<P: AsRef<Path>>(paths: &[P]) -> Result<Vec<Fi
     Ok(results)
 }
 
+/// Replace each result's path with its absolute, canonical form, resolving `.`
+/// and `..` segments and symbolic links in the parent directories. A symbolic
+/// link itself keeps its own name rather than its target's. Paths that can't be
+/// canonicalized, such as ones that no longer exist, are left unchanged.
+#[cfg(feature = "std")]
+pub fn canonicalize_paths(results: Vec<FileInfo>) -> Vec<FileInfo> {
+    results
+        .into_iter()
+        .map(|info| FileInfo {
+            path: canonical_path(&info.path).unwrap_or(info.path),
+            ..info
+        })
+        .collect()
+}
+
+/// The canonical form of `path`, without resolving it if it is a symbolic link.
+#[cfg(feature = "std")]
+pub(crate) fn canonical_path(path: &Path) -> Option<PathBuf> {
+    if !fs::symlink_metadata(path).ok()?.file_type().is_symlink() {
+        return fs::canonicalize(path).ok();
+    }
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    Some(fs::canonicalize(parent).ok()?.join(path.file_name()?))
+}
+
 /// Filter results to only include files (not directories).
+#[cfg(feature = "std")]
 pub fn filter_files(results: Vec<FileInfo>) -> Vec<FileInfo> {
     results.into_iter().filter(|info| !info.is_directory).collect()
 }
 
 /// Filter results to only include directories.
+#[cfg(feature = "std")]
 pub fn filter_directories(results: Vec<FileInfo>) -> Vec<FileInfo> {
     results.into_iter().filter(|info| info.is_directory).collect()
 }
 
+/// Filter results to only include entries of the given category, keeping their order.
+#[cfg(feature = "std")]
+pub fn filter_by_category(results: Vec<FileInfo>, category: Category) -> Vec<FileInfo> {
+    filter_by_predicate(results, |info| info.category() == category)
+}
+
+/// Filter results to only include entries satisfying `predicate`, keeping their order.
+#[cfg(feature = "std")]
+pub fn filter_by_predicate(results: Vec<FileInfo>, predicate: impl Fn(&FileInfo) -> bool) -> Vec<FileInfo> {
+    results.into_iter().filter(|info| predicate(info)).collect()
+}
+
+/// Sort results by size, largest first. Directories count as size 0.
+/// The sort is stable, so entries of equal size keep their original order.
+#[cfg(feature = "std")]
+pub fn sort_by_size_desc(mut results: Vec<FileInfo>) -> Vec<FileInfo> {
+    results.sort_by_key(|info| std::cmp::Reverse(info.size.unwrap_or(0)));
+    results
+}
+
+/// Sort results by path. The sort is stable.
+#[cfg(feature = "std")]
+pub fn sort_by_path(mut results: Vec<FileInfo>) -> Vec<FileInfo> {
+    results.sort_by(|a, b| a.path.cmp(&b.path));
+    results
+}
+
+/// Sort results by type description. The sort is stable, so entries of the
+/// same type keep their original order.
+#[cfg(feature = "std")]
+pub fn sort_by_type(mut results: Vec<FileInfo>) -> Vec<FileInfo> {
+    results.sort_by(|a, b| a.description.cmp(&b.description));
+    results
+}
+
 /// Group results by file type description.
+#[cfg(feature = "std")]
 pub fn group_by_type(results: Vec<FileInfo>) -> std::collections::HashMap<String, Vec<FileInfo>> {
     let mut grouped = std::collections::HashMap::new();
     
@@ -182,19 +1129,600 @@ pub fn group_by_type(results: Vec<FileInfo>) -> std::collections::HashMap<String
     grouped
 }
 
+/// Count results by coarse category. Directories are counted under
+/// `Category::Directory`; use `filter_files` first to leave them out.
+#[cfg(feature = "std")]
+pub fn count_by_category(results: &[FileInfo]) -> std::collections::HashMap<Category, usize> {
+    let mut counts = std::collections::HashMap::new();
+
+    for file_info in results {
+        *counts.entry(file_info.category()).or_insert(0) += 1;
+    }
+
+    counts
+}
+
 /// Identify the file types from multiple byte slices.
 /// Returns a vector of FileInfo for all processed items.
+#[cfg(feature = "std")]
 pub fn identify_many_bytes<'a, I>(files: I) -> Vec<FileInfo>
 where
     I: IntoIterator<Item = &'a [u8]>,
+{
+    files.into_iter().map(identify_bytes_or_unknown).collect()
+}
+
+/// Identify a byte slice, reporting it as "Unknown file type" if unrecognized.
+#[cfg(feature = "std")]
+fn identify_bytes_or_unknown(bytes: &[u8]) -> FileInfo {
+    identify_from_bytes(bytes).unwrap_or_else(|| create_unknown_info(PathBuf::new(), bytes.len() as u64))
+}
+
+/// Identify the file types from multiple named byte slices.
+/// Each result's `path` is set to the name supplied with its bytes, so results
+/// can be matched back to their inputs.
+#[cfg(feature = "std")]
+pub fn identify_many_named<'a, I>(files: I) -> Vec<FileInfo>
+where
+    I: IntoIterator<Item = (PathBuf, &'a [u8])>,
 {
     files
         .into_iter()
-        .map(|bytes| identify_from_bytes(bytes).unwrap_or(FileInfo {
-            path: PathBuf::new(),
-            description: "Unknown file type".to_string(),
-            is_directory: false,
-            size: Some(bytes.len() as u64),
-        }))
+        .map(|(path, bytes)| match identify_from_bytes(bytes) {
+            Some(info) => FileInfo { path, ..info },
+            None => create_unknown_info(path, bytes.len() as u64),
+        })
         .collect()
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    const PNG: &[u8] = &[0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D];
+
+    #[test]
+    fn png_has_mime_type() {
+        let info = identify_from_bytes(PNG).unwrap();
+        assert_eq!(info.mime_type.as_deref(), Some("image/png"));
+        assert_eq!(info.description, "PNG image data, 8-bit depth");
+    }
+
+    #[test]
+    fn directory_has_no_mime_type() {
+        let dir = tempfile::tempdir().unwrap();
+        let info = identify_multiple(&[dir.path()]).unwrap().remove(0);
+        assert!(info.is_directory);
+        assert_eq!(info.mime_type, None);
+    }
+
+    #[test]
+    fn reader_identifies_truncated_buffer() {
+        let mut reader = io::Cursor::new(&PNG[..8]);
+        let info = identify_from_reader(&mut reader).unwrap().unwrap();
+        assert_eq!(info.mime_type.as_deref(), Some("image/png"));
+        assert_eq!(info.size, None);
+        assert_eq!(reader.position(), 8);
+    }
+
+    #[test]
+    fn reader_consumes_at_most_header_read_len() {
+        let mut data = PNG.to_vec();
+        data.resize(header_read_len() * 2, 0);
+        let mut reader = io::Cursor::new(data);
+        identify_from_reader(&mut reader).unwrap().unwrap();
+        assert_eq!(reader.position(), header_read_len() as u64);
+    }
+
+    #[test]
+    fn large_sparse_file_reports_true_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sparse.png");
+        let file = fs::File::create(&path).unwrap();
+        io::Write::write_all(&mut &file, PNG).unwrap();
+        file.set_len(1 << 32).unwrap();
+
+        let info = identify_multiple(&[&path]).unwrap().remove(0);
+        assert_eq!(info.mime_type.as_deref(), Some("image/png"));
+        assert_eq!(info.size, Some(1 << 32));
+    }
+
+    #[test]
+    fn lenient_reports_each_path_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let valid = dir.path().join("image.png");
+        fs::write(&valid, PNG).unwrap();
+        let missing = dir.path().join("missing");
+        let locked = dir.path().join("locked.png");
+        fs::write(&locked, PNG).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+        }
+
+        let results = identify_multiple_lenient(&[&valid, &missing, &locked]);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap().path, valid);
+        assert!(matches!(&results[1], Err(FileProcessingError::PathNotFound(path)) if *path == missing));
+        // Permission bits aren't enforced for privileged users
+        if fs::File::open(&locked).is_err() {
+            assert!(matches!(&results[2], Err(FileProcessingError::Io(error)) if error.kind() == io::ErrorKind::PermissionDenied));
+        } else {
+            assert_eq!(results[2].as_ref().unwrap().path, locked);
+        }
+    }
+
+    #[test]
+    fn confidence_reflects_detection_step() {
+        assert_eq!(identify_from_bytes(PNG).unwrap().confidence, Confidence::Exact);
+
+        let blob: &[u8] = &[0x00, 0xDE, 0xAD, 0x00, 0xBE, 0xEF, 0x00, 0x01];
+        assert_eq!(identify_from_bytes(blob), None);
+        assert_eq!(identify_many_bytes([blob])[0].confidence, Confidence::Guess);
+    }
+
+    #[test]
+    fn depth_one_stops_at_immediate_children() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("a/b")).unwrap();
+        fs::write(root.join("top.png"), PNG).unwrap();
+        fs::write(root.join("a/middle.png"), PNG).unwrap();
+        fs::write(root.join("a/b/bottom.png"), PNG).unwrap();
+
+        let paths: Vec<PathBuf> = sort_by_path(identify_recursive_with_depth(root, 1).unwrap())
+            .into_iter()
+            .map(|info| info.path)
+            .collect();
+        assert_eq!(paths, [root.to_path_buf(), root.join("a"), root.join("top.png")]);
+        assert_eq!(identify_recursive(root).unwrap().len(), 6);
+    }
+
+    #[test]
+    fn extension_matching_content_is_not_a_mismatch() {
+        assert_eq!(check_extension_mismatch(Path::new("image.png"), PNG), None);
+        assert_eq!(check_extension_mismatch(Path::new("image.unknownext"), b"MZ\x90\x00"), None);
+    }
+
+    #[test]
+    fn executable_named_png_is_a_mismatch() {
+        let mismatch = check_extension_mismatch(Path::new("image.png"), b"MZ\x90\x00\x03\x00\x00\x00").unwrap();
+        assert_eq!(mismatch.extension, "png");
+        assert_eq!(mismatch.expected_mime, "image/png");
+        assert_ne!(mismatch.detected_mime, "image/png");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        for path in ["dir/sub/image.png", r"C:\dir\sub\image.png"] {
+            let info = FileInfo {
+                path: PathBuf::from(path),
+                ..identify_from_bytes(PNG).unwrap()
+            };
+            let json = serde_json::to_value(&info).unwrap();
+            assert_eq!(json["path"], path);
+            assert_eq!(json["size"], PNG.len() as u64);
+            assert_eq!(serde_json::from_value::<FileInfo>(json).unwrap(), info);
+        }
+
+        let directory = create_directory_info("dir");
+        let json = serde_json::to_value(&directory).unwrap();
+        assert!(json["size"].is_null());
+        assert_eq!(serde_json::from_value::<FileInfo>(json).unwrap(), directory);
+    }
+
+    #[test]
+    fn extension_refines_generic_text() {
+        let csv = b"name,size\nimage.png,12\n";
+        let info = identify_with_extension(Path::new("data.csv"), csv);
+        assert_eq!(info.description, "CSV (comma-separated values) data (from extension)");
+        assert_eq!(info.mime_type.as_deref(), Some("text/csv"));
+
+        let json = b"{\"name\": \"image.png\"}\n";
+        let info = identify_with_extension(Path::new("data.json"), json);
+        assert_eq!(info.mime_type.as_deref(), Some("application/json"));
+        assert_eq!(info.source, DetectionSource::Extension);
+
+        let info = identify_with_extension(Path::new("notes.txt"), csv);
+        assert_eq!(info.description, "ASCII text");
+        let info = identify_with_extension(Path::new("image.png"), csv);
+        assert_eq!(info.description, "ASCII text");
+    }
+
+    #[test]
+    fn hint_refines_generic_text() {
+        let info = identify_from_bytes_with_hint(b"name,size\nimage.png,12\n", "data.csv").unwrap();
+        assert_eq!(info.description, "CSV (comma-separated values) data");
+        assert_eq!(info.mime_type.as_deref(), Some("text/csv"));
+
+        let info = identify_from_bytes_with_hint(b"{\"name\": 1}", "data.json").unwrap();
+        assert_eq!(info.mime_type.as_deref(), Some("application/json"));
+        assert_eq!(info.path, PathBuf::from("data.json"));
+
+        let info = identify_from_bytes_with_hint(PNG, "image.json").unwrap();
+        assert_eq!(info.mime_type.as_deref(), Some("image/png"));
+    }
+
+    #[test]
+    fn named_buffers_keep_their_paths() {
+        let zip: &[u8] = b"PK\x03\x04\x14\x00";
+        let blob: &[u8] = &[0x00, 0xDE, 0xAD];
+        let results = identify_many_named([
+            (PathBuf::from("a.png"), PNG),
+            (PathBuf::from("b.zip"), zip),
+            (PathBuf::from("c.bin"), blob),
+        ]);
+
+        let paths: Vec<&Path> = results.iter().map(|info| info.path.as_path()).collect();
+        assert_eq!(paths, [Path::new("a.png"), Path::new("b.zip"), Path::new("c.bin")]);
+        assert_eq!(results[0].mime_type.as_deref(), Some("image/png"));
+        assert!(results[2].is_unknown());
+    }
+
+    #[test]
+    fn empty_input_is_reported() {
+        let info = identify_from_bytes(&[]).unwrap();
+        assert_eq!(info.description, EMPTY_DESCRIPTION);
+        assert_eq!(info.size, Some(0));
+        assert_eq!(info.file_type, FileType::Empty);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("empty.png");
+        fs::write(&path, b"").unwrap();
+        let info = identify_multiple(&[&path]).unwrap().remove(0);
+        assert_eq!(info.description, EMPTY_DESCRIPTION);
+        assert_eq!(info.size, Some(0));
+    }
+
+    fn file(path: &str, description: &str, size: u64) -> FileInfo {
+        FileInfo {
+            description: description.to_string(),
+            ..create_unknown_info(path, size)
+        }
+    }
+
+    fn paths(results: &[FileInfo]) -> Vec<&str> {
+        results.iter().map(|info| info.path.to_str().unwrap()).collect()
+    }
+
+    #[test]
+    fn size_sort_is_stable_and_counts_directories_as_zero() {
+        let results = vec![
+            file("small", "A", 1),
+            create_directory_info("dir"),
+            file("big", "B", 10),
+            file("equal1", "C", 5),
+            file("equal2", "A", 5),
+            file("empty", "D", 0),
+        ];
+        assert_eq!(paths(&sort_by_size_desc(results)), ["big", "equal1", "equal2", "small", "dir", "empty"]);
+    }
+
+    #[test]
+    fn type_and_path_sorts() {
+        let results = vec![file("c", "PNG image", 1), file("a", "Directory", 2), file("b", "PNG image", 3)];
+        assert_eq!(paths(&sort_by_type(results.clone())), ["a", "c", "b"]);
+        assert_eq!(paths(&sort_by_path(results)), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn detail_reports_signature_offset() {
+        let mut tar = vec![0; 1024];
+        tar[..8].copy_from_slice(b"file.txt");
+        tar[257..265].copy_from_slice(b"ustar\x0000");
+
+        let (info, detail) = identify_from_bytes_detailed(&tar).unwrap();
+        assert_eq!(info.description, "tar archive (POSIX ustar)");
+        assert_eq!(detail.offset, 257);
+        assert_eq!(detail.magic, b"ustar\x0000");
+        assert_eq!(detail.source, DetectionSource::CustomMagic);
+
+        let (_, detail) = identify_from_bytes_detailed(PNG).unwrap();
+        assert_eq!(detail.offset, 0);
+    }
+
+    fn detected_file(path: &str, bytes: &[u8]) -> FileInfo {
+        FileInfo {
+            path: PathBuf::from(path),
+            ..identify_bytes_or_unknown(bytes)
+        }
+    }
+
+    fn mixed_results() -> Vec<FileInfo> {
+        let zip: &[u8] = b"PK\x03\x04\x14\x00";
+        let gzip: &[u8] = b"\x1f\x8b\x08\x00\x00\x00\x00\x00";
+        vec![
+            create_directory_info("root"),
+            detected_file("root/a.png", PNG),
+            detected_file("root/b.zip", zip),
+            detected_file("root/c.png", PNG),
+            detected_file("root/d.gz", gzip),
+            detected_file("root/e.txt", b"plain text"),
+        ]
+    }
+
+    #[test]
+    fn counts_by_category() {
+        let counts = count_by_category(&mixed_results());
+        assert_eq!(counts[&Category::Image], 2);
+        assert_eq!(counts[&Category::Archive], 2);
+        assert_eq!(counts[&Category::Text], 1);
+        assert_eq!(counts[&Category::Directory], 1);
+        assert!(!count_by_category(&filter_files(mixed_results())).contains_key(&Category::Directory));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn special_files_are_labeled_without_reading() {
+        let dir = tempfile::tempdir().unwrap();
+        let fifo = dir.path().join("pipe");
+        assert!(std::process::Command::new("mkfifo").arg(&fifo).status().unwrap().success());
+        let socket = dir.path().join("socket");
+        let _listener = std::os::unix::net::UnixListener::bind(&socket).unwrap();
+
+        let results = identify_multiple(&[&fifo, &socket]).unwrap();
+        assert_eq!(results[0].description, "Named pipe (FIFO)");
+        assert_eq!(results[0].file_type, FileType::NamedPipe);
+        assert_eq!(results[1].description, "Socket");
+        assert_eq!(results[1].source, DetectionSource::Special);
+
+        let info = identify_multiple(&["/dev/null"]).unwrap().remove(0);
+        assert_eq!(info.description, "Character device");
+    }
+
+    #[test]
+    fn directory_children_are_not_recursed() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.png"), PNG).unwrap();
+        fs::create_dir(dir.path().join("sub")).unwrap();
+        fs::write(dir.path().join("sub/nested.png"), PNG).unwrap();
+
+        let results = identify_dir_children(dir.path()).unwrap();
+        assert_eq!(paths(&results), [dir.path().join("a.png").to_str().unwrap(), dir.path().join("sub").to_str().unwrap()]);
+        assert!(results[1].is_directory);
+
+        let file = dir.path().join("a.png");
+        assert!(matches!(identify_dir_children(&file), Err(FileProcessingError::NotADirectory(_))));
+    }
+
+    #[test]
+    fn strict_mode_skips_infer_and_text() {
+        let wasm = b"\0asm\x01\0\0\0";
+        assert_eq!(identify_from_bytes_strict(wasm), None);
+        assert_eq!(identify_from_bytes(wasm).unwrap().source, DetectionSource::Infer);
+
+        assert_eq!(identify_from_bytes_strict(b"plain text\n"), None);
+        let png = identify_from_bytes_strict(PNG).unwrap();
+        assert_eq!(png.confidence, Confidence::Exact);
+        assert_eq!(identify_from_bytes_strict(b"").unwrap().description, "Empty file");
+    }
+
+    #[test]
+    fn identify_through_open_handle() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("image.png");
+        fs::write(&path, PNG).unwrap();
+
+        let mut file = fs::File::open(&path).unwrap();
+        file.seek(SeekFrom::Start(4)).unwrap();
+        let info = identify_from_file(&mut file).unwrap();
+        assert_eq!(info.mime_type.as_deref(), Some("image/png"));
+        assert_eq!(info.size, Some(PNG.len() as u64));
+        assert_eq!(info.path, PathBuf::new());
+        assert_eq!(file.stream_position().unwrap(), 0);
+    }
+
+    #[test]
+    fn fresh_file_has_recent_modified_time() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("new.png");
+        let before = SystemTime::now() - std::time::Duration::from_secs(60);
+        fs::write(&path, PNG).unwrap();
+
+        let info = identify_file_from_path(&path).unwrap();
+        let modified = info.modified.unwrap();
+        assert!(modified >= before);
+        assert!(modified <= SystemTime::now() + std::time::Duration::from_secs(60));
+        assert_eq!(identify_from_bytes(PNG).unwrap().modified, None);
+        assert_eq!(identify_from_bytes(PNG).unwrap().created, None);
+    }
+
+    #[test]
+    fn identify_kind_matches_identify_from_bytes() {
+        let inputs: [&[u8]; 4] = [PNG, b"GIF89a\x01\x00", b"\xFF\xD8\xFF\xE1\x00\x10Exif", b"%PDF-1.7\n"];
+        for bytes in inputs {
+            let kind = identify_kind(bytes).unwrap();
+            assert_eq!(kind, identify_from_bytes(bytes).unwrap().description);
+        }
+        assert_eq!(identify_kind(b"plain text\n"), None);
+        assert_eq!(identify_kind(b"\0asm\x01\0\0\0"), None);
+    }
+
+    #[test]
+    fn oversized_file_is_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("big.bin");
+        fs::write(&path, [0u8; 64]).unwrap();
+
+        match identify_file_capped(&path, 16) {
+            Err(FileProcessingError::FileTooLarge { path: error_path, size, limit }) => {
+                assert_eq!((error_path, size, limit), (path.clone(), 64, 16));
+            }
+            other => panic!("expected FileTooLarge, got {other:?}"),
+        }
+        assert!(identify_file_capped(&path, 64).is_ok());
+        assert!(identify_file_capped(dir.path(), 0).unwrap().is_directory);
+    }
+
+    #[test]
+    fn canonicalized_paths_are_normalized() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("subdir")).unwrap();
+        fs::write(dir.path().join("file.png"), PNG).unwrap();
+        let input = dir.path().join(".").join("subdir").join("..").join("file.png");
+
+        let results = canonicalize_paths(identify_multiple(&[input]).unwrap());
+        assert_eq!(results[0].path, fs::canonicalize(dir.path().join("file.png")).unwrap());
+        assert!(results[0].path.is_absolute());
+    }
+
+    #[test]
+    fn canonicalizing_keeps_unresolvable_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("gone").join("..").join("file.png");
+        let results = canonicalize_paths(vec![create_unknown_info(&missing, 0)]);
+        assert_eq!(results[0].path, missing);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn dangling_symlink_is_labelled_not_fatal() {
+        let dir = tempfile::tempdir().unwrap();
+        let link = dir.path().join("dangling");
+        std::os::unix::fs::symlink(dir.path().join("missing"), &link).unwrap();
+        fs::write(dir.path().join("image.png"), PNG).unwrap();
+
+        let results = identify_multiple(&[link.clone(), dir.path().join("image.png")]).unwrap();
+        assert_eq!(results[0].description, "Broken symbolic link");
+        assert_eq!(results[0].file_type, FileType::BrokenSymlink);
+        assert_eq!(results[1].mime_type.as_deref(), Some("image/png"));
+
+        let missing = dir.path().join("missing");
+        assert!(matches!(identify_multiple(&[missing]), Err(FileProcessingError::PathNotFound(_))));
+    }
+
+    #[test]
+    fn filters_keep_only_images_in_order() {
+        let images = filter_by_category(mixed_results(), Category::Image);
+        assert_eq!(paths(&images), ["root/a.png", "root/c.png"]);
+
+        let small = filter_by_predicate(mixed_results(), |info| info.size.is_some_and(|size| size < 8));
+        assert_eq!(paths(&small), ["root/b.zip"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn executable_bit_is_reported() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run.sh");
+        fs::write(&path, b"#!/bin/sh\necho hi\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        assert_eq!(identify_file_from_path(&path).unwrap().is_executable, Some(false));
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        assert_eq!(identify_file_from_path(&path).unwrap().is_executable, Some(true));
+        assert_eq!(identify_file_from_path(dir.path()).unwrap().is_executable, None);
+    }
+
+    #[test]
+    fn preview_is_capped_and_formatted() {
+        let (info, preview) = identify_with_preview(PNG, 4);
+        assert_eq!(info.unwrap().mime_type.as_deref(), Some("image/png"));
+        assert_eq!(preview, "89 50 4e 47");
+
+        let (info, preview) = identify_with_preview(b"\x00\x01\xfe", 100);
+        assert_eq!(info, identify_from_bytes(b"\x00\x01\xfe"));
+        assert_eq!(preview, "00 01 fe");
+        assert_eq!(identify_with_preview(PNG, 0).1, "");
+    }
+
+    #[test]
+    fn unknown_results_are_flagged() {
+        let blob: &[u8] = &[0xde, 0xad, 0xbe, 0xef, 0x00, 0x01];
+        let unknown = identify_many_bytes([blob, PNG]);
+        assert!(unknown[0].is_unknown());
+        assert_eq!(unknown[0].description, UNKNOWN_DESCRIPTION);
+        assert!(!unknown[1].is_unknown());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("blob.bin");
+        fs::write(&path, blob).unwrap();
+        let from_path = identify_file_from_path(&path).unwrap();
+        assert!(from_path.is_unknown());
+        assert_eq!(from_path.description, UNKNOWN_DESCRIPTION);
+        assert!(!identify_from_bytes(b"").unwrap().is_unknown());
+    }
+
+    #[test]
+    fn filename_hint_refines_generic_zip() {
+        let xlsx_mime = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+        let archive = containers::tests::zip(&[("data.bin", b"payload")]);
+        assert_eq!(identify_from_bytes(&archive).unwrap().mime_type.as_deref(), Some("application/zip"));
+
+        let info = identify_from_bytes_with_hint(&archive, "book.xlsx").unwrap();
+        assert_eq!(info.mime_type.as_deref(), Some(xlsx_mime));
+        assert_eq!(info.source, DetectionSource::Extension);
+        assert_eq!(info.path, PathBuf::from("book.xlsx"));
+    }
+
+    #[test]
+    fn filename_hint_never_overrides_specific_content() {
+        let png = identify_from_bytes_with_hint(PNG, "book.xlsx").unwrap();
+        assert_eq!(png.mime_type.as_deref(), Some("image/png"));
+
+        let docx = containers::tests::zip(&[("[Content_Types].xml", b"<Types/>"), ("word/document.xml", b"<w:document/>")]);
+        let info = identify_from_bytes_with_hint(&docx, "book.xlsx").unwrap();
+        assert_eq!(info.description, "Microsoft Word 2007+ document (DOCX)");
+
+        assert_eq!(identify_from_bytes_with_hint(&[0xde, 0xad, 0xbe, 0xef, 0x00], "book.xlsx"), None);
+    }
+
+    #[test]
+    fn source_records_detection_step() {
+        let dir = tempfile::tempdir().unwrap();
+        let png = dir.path().join("image.png");
+        let text = dir.path().join("notes");
+        let empty = dir.path().join("empty");
+        fs::write(&png, PNG).unwrap();
+        fs::write(&text, b"just some words\n").unwrap();
+        fs::write(&empty, b"").unwrap();
+
+        let results = identify_multiple(&[&png, &text, &empty]).unwrap();
+        assert_eq!(results[0].source, DetectionSource::CustomMagic);
+        assert_eq!(results[1].source, DetectionSource::Text);
+        assert_eq!(results[2].source, DetectionSource::Empty);
+        assert_eq!(identify_file_from_path(dir.path()).unwrap().source, DetectionSource::Special);
+    }
+
+    #[test]
+    fn metadata_only_never_reads_content() {
+        let dir = tempfile::tempdir().unwrap();
+        // PNG content under a CSV name: reading it would report a PNG
+        let path = dir.path().join("table.csv");
+        fs::write(&path, PNG).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o000)).unwrap();
+        }
+
+        let info = identify_metadata_only(&path).unwrap();
+        assert_eq!(info.mime_type.as_deref(), Some("text/csv"));
+        assert!(info.description.ends_with("(from extension)"));
+        assert_eq!((info.source, info.confidence), (DetectionSource::Extension, Confidence::Guess));
+        assert_eq!(info.size, Some(PNG.len() as u64));
+        assert!(identify_metadata_only(dir.path()).unwrap().is_directory);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn path_beyond_max_path_is_identified() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut nested = dir.path().to_path_buf();
+        while nested.as_os_str().len() <= 260 {
+            nested.push("d".repeat(40));
+        }
+        fs::create_dir_all(&nested).unwrap();
+        let path = nested.join("image.png");
+        fs::write(&path, PNG).unwrap();
+
+        let info = identify_file_from_path(&path).unwrap();
+        assert_eq!(info.mime_type.as_deref(), Some("image/png"));
+        let results = identify_recursive(dir.path()).unwrap();
+        assert!(results.iter().any(|info| info.path == path && info.mime_type.as_deref() == Some("image/png")));
+    }
+}
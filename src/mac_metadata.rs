@@ -0,0 +1,95 @@
+//! Recognition of the metadata files macOS leaves next to user files.
+
+use std::ffi::OsStr;
+use std::path::Path;
+
+use crate::{Confidence, DetectionSource, FileInfo, FileType, create_unknown_info};
+
+/// Description of `.DS_Store` files in the built-in magic table.
+const DS_STORE_DESCRIPTION: &str = "macOS Finder folder metadata (.DS_Store)";
+/// MIME type of AppleDouble files in the built-in magic table.
+const APPLE_DOUBLE_MIME: &str = "multipart/appledouble";
+/// Directory that the macOS archiver stores AppleDouble files in.
+const MACOSX_DIR: &str = "__MACOSX";
+const DS_STORE_NAME: &str = ".DS_Store";
+
+/// Whether a result is macOS metadata rather than user content: a `.DS_Store`
+/// file, an AppleDouble `._name` file, or anything in a `__MACOSX` directory,
+/// as found in archives created on macOS. Both the detected type and the file
+/// name are checked, so metadata is recognized even when the content is truncated
+/// or the file has been renamed.
+pub fn is_mac_metadata(info: &FileInfo) -> bool {
+    let detected = info.mime_type.as_deref() == Some(APPLE_DOUBLE_MIME)
+        || matches!(&info.file_type, FileType::Unknown(description) if description == DS_STORE_DESCRIPTION);
+
+    let file_name = info.path.file_name().map(OsStr::to_string_lossy);
+    let named = file_name.is_some_and(|name| name == DS_STORE_NAME || name.starts_with("._"));
+    let in_macosx_dir = info.path.components().any(|component| component.as_os_str() == MACOSX_DIR);
+
+    detected || named || in_macosx_dir
+}
+
+/// The FileInfo for a file of `size` bytes named `.DS_Store` whose content
+/// wasn't recognized, e.g. because it is truncated or from an older Finder.
+/// Returns None for other file names.
+pub(crate) fn ds_store_info(path: &Path, size: u64) -> Option<FileInfo> {
+    (path.file_name()? == DS_STORE_NAME).then(|| FileInfo {
+        description: DS_STORE_DESCRIPTION.to_string(),
+        file_type: FileType::Unknown(DS_STORE_DESCRIPTION.to_string()),
+        source: DetectionSource::Extension,
+        confidence: Confidence::Guess,
+        ..create_unknown_info(path, size)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    use crate::{identify_file_from_path, identify_from_bytes};
+
+    const DS_STORE: &[u8] = b"\x00\x00\x00\x01Bud1\x00\x00\x10\x00";
+    const APPLE_DOUBLE: &[u8] = b"\x00\x05\x16\x07\x00\x02\x00\x00Mac OS X        ";
+
+    #[test]
+    fn ds_store_by_magic() {
+        let info = identify_from_bytes(DS_STORE).unwrap();
+        assert_eq!(info.description, DS_STORE_DESCRIPTION);
+        assert_eq!(info.source, DetectionSource::CustomMagic);
+        assert!(is_mac_metadata(&info));
+    }
+
+    #[test]
+    fn ds_store_by_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(DS_STORE_NAME);
+        fs::write(&path, [0xde, 0xad, 0xbe, 0xef, 0x00, 0x01]).unwrap();
+
+        let info = identify_file_from_path(&path).unwrap();
+        assert_eq!(info.description, DS_STORE_DESCRIPTION);
+        assert_eq!(info.source, DetectionSource::Extension);
+        assert!(is_mac_metadata(&info));
+
+        let other = dir.path().join("other");
+        fs::write(&other, [0xde, 0xad, 0xbe, 0xef, 0x00, 0x01]).unwrap();
+        assert!(identify_file_from_path(&other).unwrap().is_unknown());
+    }
+
+    #[test]
+    fn apple_double_by_magic() {
+        let info = identify_from_bytes(APPLE_DOUBLE).unwrap();
+        assert_eq!(info.mime_type.as_deref(), Some(APPLE_DOUBLE_MIME));
+        assert!(is_mac_metadata(&info));
+    }
+
+    #[test]
+    fn mac_metadata_by_path() {
+        let png = identify_from_bytes(b"\x89PNG\r\n\x1a\n").unwrap();
+        assert!(!is_mac_metadata(&png));
+        assert!(is_mac_metadata(&FileInfo { path: "photos/._image.png".into(), ..png.clone() }));
+        assert!(is_mac_metadata(&FileInfo { path: "__MACOSX/photos/image.png".into(), ..png.clone() }));
+        assert!(!is_mac_metadata(&FileInfo { path: "photos/image.png".into(), ..png }));
+    }
+}
@@ -0,0 +1,132 @@
+//! Magic number definitions loaded at runtime, enabled by the `serde` feature.
+
+use serde::Deserialize;
+
+use crate::MagicEntry;
+
+/// Error loading magic number definitions with `load_magic_from_str`.
+#[derive(Debug, thiserror::Error)]
+pub enum MagicParseError {
+    #[error("Invalid magic definition file: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Entry {index}: invalid hex magic {magic:?}")]
+    InvalidMagic { index: usize, magic: String },
+    #[error("Entry {index}: empty magic")]
+    EmptyMagic { index: usize },
+}
+
+/// One entry as written in a definition file.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MagicDefinition {
+    #[serde(default)]
+    offset: usize,
+    magic: String,
+    description: String,
+    #[serde(default)]
+    mime: Option<String>,
+    #[serde(default)]
+    from_end: bool,
+}
+
+/// Parse magic number entries from a JSON array of definitions, for use with
+/// `MagicRegistry::register`. Each definition has a `magic` hex string (bytes
+/// may be separated by spaces) and a `description`, plus optional `offset`
+/// (default 0), `mime` and `from_end` (default false):
+///
+/// ```json
+/// [{ "offset": 0, "magic": "4D 59 46 4D", "description": "MyFormat data", "mime": "application/x-myformat" }]
+/// ```
+///
+/// Fails on malformed JSON and on entries whose magic is empty or not valid hex.
+/// `MagicEntry` holds `'static` data, so the strings and bytes of loaded
+/// entries are leaked; load definitions once rather than repeatedly.
+pub fn load_magic_from_str(definitions: &str) -> Result<Vec<MagicEntry>, MagicParseError> {
+    let definitions: Vec<MagicDefinition> = serde_json::from_str(definitions)?;
+
+    definitions
+        .into_iter()
+        .enumerate()
+        .map(|(index, definition)| {
+            let magic = parse_hex(&definition.magic).ok_or_else(|| MagicParseError::InvalidMagic {
+                index,
+                magic: definition.magic.clone(),
+            })?;
+            if magic.is_empty() {
+                return Err(MagicParseError::EmptyMagic { index });
+            }
+
+            Ok(MagicEntry {
+                offset: definition.offset,
+                magic: magic.leak(),
+                description: definition.description.leak(),
+                mime: definition.mime.map(|mime| &*mime.leak()),
+                from_end: definition.from_end,
+                search_window: None,
+            })
+        })
+        .collect()
+}
+
+/// Bytes of a hex string such as "89504E47" or "89 50 4e 47".
+/// Returns None for non-hex characters or an odd number of digits.
+fn parse_hex(hex: &str) -> Option<Vec<u8>> {
+    let digits: Vec<u8> = hex
+        .bytes()
+        .filter(|byte| !byte.is_ascii_whitespace())
+        .map(|byte| char::from(byte).to_digit(16).map(|digit| digit as u8))
+        .collect::<Option<_>>()?;
+
+    if !digits.len().is_multiple_of(2) {
+        return None;
+    }
+    Some(digits.chunks(2).map(|pair| pair[0] << 4 | pair[1]).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::MagicRegistry;
+
+    #[test]
+    fn valid_definitions_are_usable_by_registry() {
+        let entries = load_magic_from_str(
+            r#"[
+                { "offset": 0, "magic": "4D 59 46 4D", "description": "MyFormat data", "mime": "application/x-myformat" },
+                { "offset": 4, "magic": "7461696c", "description": "Tail format", "from_end": true }
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].magic, b"MYFM");
+        assert_eq!(entries[0].mime, Some("application/x-myformat"));
+        assert_eq!((entries[1].offset, entries[1].magic, entries[1].mime), (4, &b"tail"[..], None));
+        assert!(entries[1].from_end);
+
+        let mut registry = MagicRegistry::new();
+        for entry in entries {
+            registry.register(entry);
+        }
+        let info = registry.identify_from_bytes_with(b"MYFM payload").unwrap();
+        assert_eq!(info.description, "MyFormat data");
+    }
+
+    #[test]
+    fn malformed_definitions_are_rejected() {
+        let error = load_magic_from_str(r#"[{ "magic": "4D5", "description": "Odd digits" }]"#).unwrap_err();
+        assert!(matches!(error, MagicParseError::InvalidMagic { index: 0, .. }));
+
+        let error = load_magic_from_str(
+            r#"[{ "magic": "00", "description": "Fine" }, { "magic": "zz", "description": "Not hex" }]"#,
+        )
+        .unwrap_err();
+        assert!(matches!(error, MagicParseError::InvalidMagic { index: 1, .. }));
+
+        let error = load_magic_from_str(r#"[{ "magic": " ", "description": "Blank" }]"#).unwrap_err();
+        assert!(matches!(error, MagicParseError::EmptyMagic { index: 0 }));
+
+        assert!(matches!(load_magic_from_str("not json"), Err(MagicParseError::Json(_))));
+    }
+}
@@ -0,0 +1,60 @@
+//! Memory-mapped header reads, enabled by the `mmap` feature.
+
+use std::fs;
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use crate::{FileInfo, FileProcessingError, header_read_len, identify_file_from_path, identify_header, tail_read_len, with_metadata};
+
+/// Files smaller than this are read normally, since mapping them costs more
+/// than copying their header.
+const MIN_MMAP_LEN: u64 = 64 * 1024;
+
+/// Identify a single file or directory by memory-mapping it, so the header and
+/// any trailer are sliced from the mapping instead of copied into a buffer.
+/// Directories, special files and files under 64 KiB are handled as usual. Results are the same as for
+/// `identify_multiple`.
+pub fn identify_file_mmap(path: &Path) -> Result<FileInfo, FileProcessingError> {
+    let Ok(metadata) = fs::metadata(path) else {
+        return identify_file_from_path(path);
+    };
+
+    if !metadata.is_file() || metadata.len() < MIN_MMAP_LEN {
+        return identify_file_from_path(path);
+    }
+
+    let file = fs::File::open(path)?;
+    // SAFETY: the mapping is read-only and only lives for this call. If another
+    // process truncates the file meanwhile, the read may fault; that risk is
+    // why mapping is opt-in behind the `mmap` feature.
+    let map = unsafe { Mmap::map(&file)? };
+
+    let head = &map[..map.len().min(header_read_len())];
+    let tail = &map[map.len().saturating_sub(tail_read_len())..];
+    Ok(with_metadata(identify_header(path, head, tail, map.len() as u64), &metadata))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::identify_multiple;
+
+    #[test]
+    fn mmap_matches_standard_read() {
+        let dir = tempfile::tempdir().unwrap();
+        let large = dir.path().join("large.png");
+        let mut bytes = b"\x89PNG\r\n\x1a\n".to_vec();
+        bytes.resize(MIN_MMAP_LEN as usize * 2, 0);
+        fs::write(&large, bytes).unwrap();
+        let small = dir.path().join("small.png");
+        fs::write(&small, b"\x89PNG\r\n\x1a\n").unwrap();
+
+        for path in [&large, &small] {
+            let info = identify_file_mmap(path).unwrap();
+            assert_eq!(info.mime_type.as_deref(), Some("image/png"));
+            assert_eq!(info, identify_multiple(&[path]).unwrap().remove(0));
+        }
+    }
+}
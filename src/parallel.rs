@@ -0,0 +1,99 @@
+//! Multi-threaded variants of the identification functions, enabled by the `parallel` feature.
+
+use std::path::Path;
+
+use rayon::prelude::*;
+use walkdir::WalkDir;
+
+use crate::traversal::identify_entry;
+use crate::{FileInfo, FileProcessingError, TraversalOptions, identify_bytes_or_unknown};
+
+/// Recursively process a directory and all its contents using a rayon thread pool.
+/// The directory is walked first, then entries are identified in parallel.
+/// Results are not guaranteed to be in traversal order. As with
+/// `identify_recursive`, symbolic links are reported as "Symbolic link" rather
+/// than identified by their target.
+pub fn identify_recursive_parallel<P: AsRef<Path>>(path: P) -> Result<Vec<FileInfo>, FileProcessingError> {
+    let path = path.as_ref();
+
+    if !path.exists() {
+        return Err(FileProcessingError::PathNotFound(path.to_path_buf()));
+    }
+
+    let options = TraversalOptions::default();
+    let entries = WalkDir::new(path).into_iter().collect::<Result<Vec<_>, _>>()?;
+
+    entries
+        .par_iter()
+        .map(|entry| identify_entry(entry, &options))
+        .collect()
+}
+
+/// Identify the file types from multiple byte slices using a rayon thread pool.
+/// Results are the same as from `identify_many_bytes`, in input order.
+pub fn identify_many_bytes_parallel<B: AsRef<[u8]> + Sync>(files: &[B]) -> Vec<FileInfo> {
+    files.par_iter().map(|bytes| identify_bytes_or_unknown(bytes.as_ref())).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    use crate::identify_recursive;
+
+    fn sorted(mut results: Vec<FileInfo>) -> Vec<FileInfo> {
+        results.sort_by(|a, b| a.path.cmp(&b.path));
+        results
+    }
+
+    #[test]
+    fn parallel_matches_sequential() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("sub")).unwrap();
+        fs::write(dir.path().join("image.png"), b"\x89PNG\r\n\x1a\n").unwrap();
+        fs::write(dir.path().join("sub/notes.txt"), b"hello").unwrap();
+        fs::write(dir.path().join("sub/archive.zip"), b"PK\x03\x04").unwrap();
+
+        let parallel = identify_recursive_parallel(dir.path()).unwrap();
+        let sequential = identify_recursive(dir.path()).unwrap();
+        assert_eq!(parallel.len(), 5);
+        assert_eq!(sorted(parallel), sorted(sequential));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn parallel_reports_symlinks_unfollowed() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("image.png"), b"\x89PNG\r\n\x1a\n").unwrap();
+        std::os::unix::fs::symlink(dir.path().join("image.png"), dir.path().join("link.png")).unwrap();
+
+        let link = identify_recursive_parallel(dir.path())
+            .unwrap()
+            .into_iter()
+            .find(|info| info.path.ends_with("link.png"))
+            .unwrap();
+        assert_eq!(link.description, "Symbolic link");
+    }
+
+    #[test]
+    fn parallel_bytes_match_sequential_in_order() {
+        let inputs: Vec<Vec<u8>> = (0..200)
+            .map(|i| match i % 4 {
+                0 => b"\x89PNG\r\n\x1a\n".to_vec(),
+                1 => format!("line {i}\n").into_bytes(),
+                2 => vec![0xde, 0xad, 0xbe, 0xef, i as u8],
+                _ => Vec::new(),
+            })
+            .collect();
+
+        let parallel = identify_many_bytes_parallel(&inputs);
+        let sequential = crate::identify_many_bytes(inputs.iter().map(Vec::as_slice));
+        assert_eq!(parallel.len(), inputs.len());
+        for (parallel, sequential) in parallel.iter().zip(&sequential) {
+            assert_eq!(parallel, sequential);
+        }
+        assert_eq!(parallel[2].description, "Unknown file type");
+    }
+}
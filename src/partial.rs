@@ -0,0 +1,96 @@
+//! Identification of incomplete inputs, such as files still being written.
+
+use crate::core_detect::{best_magic_match, entry_matches};
+use crate::magicnums::{MAGIC_NUMBERS, MagicEntry};
+use crate::{FileInfo, identify_from_bytes};
+
+/// Outcome of identifying a prefix of an input that may still grow.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartialResult {
+    /// No signature that could still match would change this result
+    Determined(FileInfo),
+    /// A more specific signature extends past the available bytes and still
+    /// matches what is there, so the result could change
+    NeedMoreBytes {
+        /// Total length needed before that signature can be checked
+        at_least: usize,
+    },
+    /// Nothing matches and no signature is waiting on more bytes
+    Unknown,
+}
+
+/// Identify a file from a possibly incomplete prefix of its contents, reporting
+/// whether more bytes could change the answer. Signatures matched against the
+/// end of the input are ignored, since a growing input has no stable end.
+pub fn identify_partial(bytes: &[u8]) -> PartialResult {
+    let heads: Vec<&MagicEntry> = MAGIC_NUMBERS
+        .iter()
+        .filter(|entry| !entry.from_end || entry.search_window.is_some())
+        .collect();
+    let best = best_magic_match(heads.iter().copied(), bytes, &[]);
+
+    let at_least = heads
+        .iter()
+        .filter(|entry| may_match_later(entry, bytes))
+        .filter(|entry| best.is_none_or(|best| (entry.magic.len(), entry.offset) > (best.magic.len(), best.offset)))
+        .map(|entry| entry.head_len())
+        .min();
+
+    if let Some(at_least) = at_least {
+        return PartialResult::NeedMoreBytes { at_least };
+    }
+
+    match identify_from_bytes(bytes) {
+        Some(info) => PartialResult::Determined(info),
+        None => PartialResult::Unknown,
+    }
+}
+
+/// Whether `entry` extends past the end of `bytes` and the part of its magic
+/// that is available matches.
+fn may_match_later(entry: &MagicEntry, bytes: &[u8]) -> bool {
+    if entry.head_len() <= bytes.len() {
+        return false;
+    }
+    if entry.search_window.is_some() {
+        // Not found in the part of the window that's available
+        return !entry_matches(entry, bytes, &[]);
+    }
+    let available = bytes.get(entry.offset..).unwrap_or_default();
+    entry.magic.starts_with(available)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::header_read_len;
+
+    fn tar_header() -> Vec<u8> {
+        let mut tar = vec![0; 512];
+        tar[..8].copy_from_slice(b"file.txt");
+        tar[257..265].copy_from_slice(b"ustar\x0000");
+        tar
+    }
+
+    #[test]
+    fn one_byte_short_of_a_signature_needs_more() {
+        assert_eq!(identify_partial(&tar_header()[..264]), PartialResult::NeedMoreBytes { at_least: 265 });
+    }
+
+    #[test]
+    fn complete_signature_is_determined() {
+        let PartialResult::Determined(info) = identify_partial(&tar_header()) else {
+            panic!("expected a determined result");
+        };
+        assert_eq!(info.mime_type.as_deref(), Some("application/x-tar"));
+    }
+
+    #[test]
+    fn mismatching_prefix_is_not_waited_on() {
+        let mut tar = tar_header();
+        tar[257] = b'X';
+        assert_ne!(identify_partial(&tar[..264]), PartialResult::NeedMoreBytes { at_least: 265 });
+        assert_eq!(identify_partial(&vec![0x01; header_read_len()]), PartialResult::Unknown);
+    }
+}
@@ -0,0 +1,67 @@
+//! Details of PDF documents beyond their type.
+
+use crate::containers::find;
+
+const PDF_SIG: &[u8] = b"%PDF-";
+/// PDF readers accept a header anywhere in the first kilobyte
+const PDF_SEARCH_LEN: usize = 1024;
+const ENCRYPT_KEY: &[u8] = b"/Encrypt";
+
+/// Version and encryption status of a PDF document, from `pdf_info`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PdfInfo {
+    /// Major version from the `%PDF-x.y` header
+    pub major: u8,
+    /// Minor version from the `%PDF-x.y` header
+    pub minor: u8,
+    /// Whether the trailer references an `/Encrypt` dictionary
+    pub encrypted: bool,
+}
+
+/// Read the version from a PDF header and check for an encryption dictionary.
+/// The trailer holding the `/Encrypt` reference is at the end of the file, so
+/// pass the whole document to detect encryption reliably.
+/// Returns None if the input has no `%PDF-x.y` header in its first kilobyte.
+pub fn pdf_info(bytes: &[u8]) -> Option<PdfInfo> {
+    let start = header_offset(bytes)? + PDF_SIG.len();
+
+    let (major, minor) = match bytes.get(start..start + 3)? {
+        &[major, b'.', minor] if major.is_ascii_digit() && minor.is_ascii_digit() => (major - b'0', minor - b'0'),
+        _ => return None,
+    };
+
+    Some(PdfInfo {
+        major,
+        minor,
+        encrypted: find(bytes, ENCRYPT_KEY).is_some(),
+    })
+}
+
+/// Position of the `%PDF-` header, within the range readers search.
+pub(crate) fn header_offset(bytes: &[u8]) -> Option<usize> {
+    find(&bytes[..bytes.len().min(PDF_SEARCH_LEN)], PDF_SIG)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_pdf_version() {
+        let info = pdf_info(b"%PDF-1.7\n%\xe2\xe3\xcf\xd3\n1 0 obj\n").unwrap();
+        assert_eq!(info, PdfInfo { major: 1, minor: 7, encrypted: false });
+    }
+
+    #[test]
+    fn encryption_dictionary_is_detected() {
+        let pdf = b"%PDF-1.4\n1 0 obj\nendobj\ntrailer\n<< /Root 1 0 R /Encrypt 5 0 R >>\n%%EOF\n";
+        assert_eq!(pdf_info(pdf), Some(PdfInfo { major: 1, minor: 4, encrypted: true }));
+    }
+
+    #[test]
+    fn non_pdf_input() {
+        assert_eq!(pdf_info(b"\x89PNG\r\n\x1a\n"), None);
+        assert_eq!(pdf_info(b"%PDF-x"), None);
+    }
+}
@@ -0,0 +1,110 @@
+//! Detection of polyglot files, which are valid in more than one format.
+
+use std::path::PathBuf;
+
+use crate::containers::{find, zip_kind};
+use crate::pdf::header_offset;
+use crate::{Confidence, DetectionSource, FileInfo, FileType, MagicRegistry};
+
+/// ZIP end of central directory record, which readers locate from the end
+const ZIP_EOCD_SIG: &[u8] = b"PK\x05\x06";
+const ZIP_LOCAL_HEADER_SIG: &[u8] = b"PK\x03\x04";
+/// Fixed EOCD length plus the longest possible archive comment
+const ZIP_EOCD_SEARCH_LEN: usize = 22 + 65535;
+
+/// Every distinct format `bytes` is valid as, for spotting polyglots such as a
+/// GIF that is also a JAR. Starts from the matches of `identify_all_matches`,
+/// leaving out short signatures that only match inside a longer one, and adds
+/// ZIP archives appended to other data and PDF headers shifted past the start
+/// of the file, which readers of those formats accept.
+/// Each format is listed once, keyed by MIME type. More than one entry means
+/// the input is a potential polyglot; an empty Vec means nothing matched.
+pub fn detect_polyglot(bytes: &[u8]) -> Vec<FileInfo> {
+    let mut results: Vec<FileInfo> = Vec::new();
+
+    let candidates = MagicRegistry::default()
+        .independent_matches(bytes)
+        .into_iter()
+        .chain(appended_zip(bytes))
+        .chain(shifted_pdf(bytes));
+
+    for info in candidates {
+        let is_new = !results.iter().any(|result| match (&result.mime_type, &info.mime_type) {
+            (Some(known), Some(mime)) => known == mime,
+            _ => result.description == info.description,
+        });
+        if is_new {
+            results.push(info);
+        }
+    }
+
+    results
+}
+
+/// A ZIP archive whose central directory is at the end of `bytes` but which
+/// doesn't start at the beginning, refined into ZIP-based formats if possible.
+fn appended_zip(bytes: &[u8]) -> Option<FileInfo> {
+    if bytes.starts_with(b"PK") {
+        return None;
+    }
+    let search_start = bytes.len().saturating_sub(ZIP_EOCD_SEARCH_LEN);
+    find(&bytes[search_start..], ZIP_EOCD_SIG)?;
+
+    let (description, mime) = find(bytes, ZIP_LOCAL_HEADER_SIG)
+        .and_then(|start| zip_kind(&bytes[start..]))
+        .unwrap_or(("Zip archive data", Some("application/zip")));
+    Some(embedded(description, mime, bytes))
+}
+
+/// A PDF header past the start of `bytes`, within the range readers search.
+fn shifted_pdf(bytes: &[u8]) -> Option<FileInfo> {
+    match header_offset(bytes)? {
+        0 => None,
+        _ => Some(embedded("PDF document", Some("application/pdf"), bytes)),
+    }
+}
+
+/// The FileInfo for a format found embedded in `bytes` rather than at its
+/// usual position.
+fn embedded(description: &str, mime: Option<&str>, bytes: &[u8]) -> FileInfo {
+    FileInfo {
+        path: PathBuf::new(),
+        description: description.to_string(),
+        file_type: FileType::detected(mime, description),
+        mime_type: mime.map(str::to_string),
+        is_directory: false,
+        size: Some(bytes.len() as u64),
+        confidence: Confidence::Probable,
+        modified: None,
+        created: None,
+        is_executable: None,
+        source: DetectionSource::CustomMagic,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::containers::tests::zip;
+
+    fn mimes(results: &[FileInfo]) -> Vec<&str> {
+        results.iter().filter_map(|info| info.mime_type.as_deref()).collect()
+    }
+
+    #[test]
+    fn gif_with_appended_jar_is_a_polyglot() {
+        let mut bytes = b"GIF89a\x01\x00\x01\x00\x00\x00\x00;".to_vec();
+        bytes.extend(zip(&[("META-INF/MANIFEST.MF", b"Manifest-Version: 1.0\n")]));
+
+        let results = detect_polyglot(&bytes);
+        assert_eq!(mimes(&results), ["image/gif", "application/java-archive"]);
+        assert_eq!(results[1].confidence, Confidence::Probable);
+    }
+
+    #[test]
+    fn plain_gif_has_a_single_identity() {
+        let results = detect_polyglot(b"GIF89a\x01\x00\x01\x00\x00\x00\x00;");
+        assert_eq!(mimes(&results), ["image/gif"]);
+    }
+}
@@ -0,0 +1,583 @@
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+use std::ops::Range;
+use std::path::PathBuf;
+
+use crate::containers::{cfb_kind, zip_kind};
+use crate::core_detect::{best_magic_match, entry_matches, match_offset};
+use crate::magicnums::{MAGIC_NUMBERS, MagicEntry};
+use crate::shebang::shebang_kind;
+use crate::text::{TextKind, classify_text};
+use crate::xml::xml_kind;
+use crate::{Category, Confidence, DetectionDetail, DetectionSource, EMPTY_DESCRIPTION, FileInfo, FileType, describe_infer_type};
+
+/// A pair of magic entries where every input matching `specific` also matches
+/// `general`, from `MagicRegistry::validate`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MagicConflict {
+    /// The entry whose signature lies within the other's
+    pub general: MagicEntry,
+    /// The entry with the longer signature
+    pub specific: MagicEntry,
+    /// Whether `specific` is a built-in entry. Custom entries are checked
+    /// before the built-ins, so `general` then always hides it
+    pub specific_is_builtin: bool,
+}
+
+/// How `MagicRegistry::merge` resolves two entries with the same signature
+/// but different descriptions or MIME types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MergePolicy {
+    /// Keep the entry already in the registry
+    PreferExisting,
+    /// Replace the existing entry with the incoming one, in place
+    PreferIncoming,
+    /// Fail the merge, leaving the registry unchanged
+    Error,
+}
+
+/// Two entries with the same signature, from `MagicRegistry::merge` with
+/// `MergePolicy::Error`.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Conflicting magic entries for {magic:02x?} at offset {offset}: {existing_description:?} and {incoming_description:?}")]
+pub struct MergeConflict {
+    /// The shared magic bytes
+    pub magic: &'static [u8],
+    /// The shared offset
+    pub offset: usize,
+    /// Description of the entry already in the registry
+    pub existing_description: &'static str,
+    /// MIME type of the entry already in the registry
+    pub existing_mime: Option<&'static str>,
+    /// Description of the entry from the registry being merged in
+    pub incoming_description: &'static str,
+    /// MIME type of the entry from the registry being merged in
+    pub incoming_mime: Option<&'static str>,
+}
+
+/// A set of user-supplied magic number entries layered on top of the built-in table.
+#[derive(Debug, Clone, Default)]
+pub struct MagicRegistry {
+    entries: Vec<MagicEntry>,
+}
+
+impl MagicRegistry {
+    /// Create a registry containing only the built-in magic numbers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a custom magic entry. Custom entries are checked before the built-ins.
+    pub fn register(&mut self, entry: MagicEntry) {
+        self.entries.push(entry);
+    }
+
+    /// Add the custom entries of `other` after this registry's own.
+    /// Entries identical to one already present are skipped. An entry with the
+    /// same signature (offset, magic, `from_end` and search window) as an
+    /// existing one but a different description or MIME type is resolved by
+    /// `policy`; with `MergePolicy::Error` the first such pair is returned and
+    /// nothing is merged.
+    pub fn merge(&mut self, other: MagicRegistry, policy: MergePolicy) -> Result<(), MergeConflict> {
+        let mut merged = self.entries.clone();
+
+        for incoming in other.entries {
+            let Some(existing) = merged.iter_mut().find(|existing| same_signature(existing, &incoming)) else {
+                merged.push(incoming);
+                continue;
+            };
+            if *existing == incoming {
+                continue;
+            }
+
+            match policy {
+                MergePolicy::PreferExisting => {}
+                MergePolicy::PreferIncoming => *existing = incoming,
+                MergePolicy::Error => {
+                    return Err(MergeConflict {
+                        magic: incoming.magic,
+                        offset: incoming.offset,
+                        existing_description: existing.description,
+                        existing_mime: existing.mime,
+                        incoming_description: incoming.description,
+                        incoming_mime: incoming.mime,
+                    });
+                }
+            }
+        }
+
+        self.entries = merged;
+        Ok(())
+    }
+
+    /// The custom entries registered so far, in registration order.
+    pub fn entries(&self) -> &[MagicEntry] {
+        &self.entries
+    }
+
+    /// Find custom entries that overlap other entries: a custom entry whose
+    /// signature is contained in another custom entry's at the same position,
+    /// which makes detection depend on signature length, or in a built-in
+    /// entry's, which it then hides entirely. Identical custom entries are
+    /// reported too. Entries with a search window aren't checked.
+    /// Returns an empty Vec if there are no conflicts.
+    pub fn validate(&self) -> Vec<MagicConflict> {
+        let mut conflicts = Vec::new();
+
+        for (index, entry) in self.entries.iter().enumerate() {
+            for other in &self.entries[index + 1..] {
+                if covers(entry, other) {
+                    conflicts.push(conflict(entry, other, false));
+                } else if covers(other, entry) {
+                    conflicts.push(conflict(other, entry, false));
+                }
+            }
+            for builtin in MAGIC_NUMBERS {
+                if covers(entry, builtin) {
+                    conflicts.push(conflict(entry, builtin, true));
+                }
+            }
+        }
+
+        conflicts
+    }
+
+    /// Identify the file type from a byte slice using this registry.
+    /// Empty input is reported as "Empty file". Otherwise custom entries are tried
+    /// first, then the built-in table, then the `infer` fallback, and finally text
+    /// classification.
+    /// Returns Some(FileInfo) if recognized, or None otherwise.
+    pub fn identify_from_bytes_with(&self, bytes: &[u8]) -> Option<FileInfo> {
+        self.identify_detailed(bytes).map(|(info, _)| info)
+    }
+
+    /// Like `identify_from_bytes_with`, but also reports which signature or
+    /// fallback produced the result.
+    pub fn identify_detailed(&self, bytes: &[u8]) -> Option<(FileInfo, DetectionDetail)> {
+        self.identify_parts(bytes, bytes)
+    }
+
+    /// Identify a file from its first bytes, `head`, and its last bytes, `tail`,
+    /// which may overlap or be the same slice. Entries flagged `from_end` are
+    /// matched against `tail`; everything else only looks at `head`.
+    pub(crate) fn identify_parts(&self, head: &[u8], tail: &[u8]) -> Option<(FileInfo, DetectionDetail)> {
+        self.identify_parts_restricted(head, tail, None)
+    }
+
+    /// Like `identify_parts`, but with `categories` the `infer` fallback only
+    /// reports types in those categories. The magic table and text
+    /// classification are unaffected.
+    pub(crate) fn identify_parts_restricted(
+        &self,
+        head: &[u8],
+        tail: &[u8],
+        categories: Option<&BTreeSet<Category>>,
+    ) -> Option<(FileInfo, DetectionDetail)> {
+        empty_match(head)
+            .or_else(|| self.magic_match(head, tail))
+            .or_else(|| infer_match(head).filter(|(info, _)| categories.is_none_or(|categories| categories.contains(&info.category()))))
+            .or_else(|| text_match(head))
+    }
+
+    /// The best custom entry matching `head` and `tail`, or failing that the
+    /// best built-in entry.
+    pub(crate) fn magic_match(&self, head: &[u8], tail: &[u8]) -> Option<(FileInfo, DetectionDetail)> {
+        let (entry, is_custom) = best_magic_match(&self.entries, head, tail)
+            .map(|entry| (entry, true))
+            .or_else(|| best_magic_match(MAGIC_NUMBERS, head, tail).map(|entry| (entry, false)))?;
+
+        Some((
+            magic_info(entry, is_custom, head),
+            DetectionDetail {
+                magic: entry.magic.to_vec(),
+                offset: match_offset(entry, head, tail).unwrap_or(entry.offset),
+                matched_bytes: entry.magic.len(),
+                source: DetectionSource::CustomMagic,
+            },
+        ))
+    }
+
+    /// Every type `bytes` could be, most specific first: all matching custom
+    /// entries, then all matching built-in entries, each ordered as in
+    /// `identify_from_bytes_with` (longest magic, then largest offset, then
+    /// table order), followed by the `infer` result if its MIME type isn't
+    /// already listed. Text classification is not included.
+    /// Returns an empty Vec if nothing matches.
+    pub fn identify_all_matches(&self, bytes: &[u8]) -> Vec<FileInfo> {
+        let mut results: Vec<FileInfo> = self
+            .matching_entries(bytes)
+            .into_iter()
+            .map(|(entry, is_custom)| magic_info(entry, is_custom, bytes))
+            .collect();
+
+        if let Some(kind) = infer::get(bytes) {
+            let info = infer_info(&kind, bytes);
+            if !results.iter().any(|result| result.mime_type == info.mime_type) {
+                results.push(info);
+            }
+        }
+
+        results
+    }
+
+    /// Like `identify_all_matches`, but without matches that are just weaker
+    /// readings of the same bytes: an entry is dropped if the bytes its magic
+    /// matched lie within those of a longer match. The `infer` result is only
+    /// included if no entry matched.
+    pub(crate) fn independent_matches(&self, bytes: &[u8]) -> Vec<FileInfo> {
+        let mut kept: Vec<(&MagicEntry, bool, Range<usize>)> = Vec::new();
+
+        for (entry, is_custom) in self.matching_entries(bytes) {
+            let Some(range) = match_range(entry, bytes) else { continue };
+            let covered = kept.iter().any(|(other, _, other_range)| {
+                other.magic.len() > entry.magic.len()
+                    && other_range.start <= range.start
+                    && range.end <= other_range.end
+            });
+            if !covered {
+                kept.push((entry, is_custom, range));
+            }
+        }
+
+        if kept.is_empty() {
+            return infer_match(bytes).map(|(info, _)| info).into_iter().collect();
+        }
+        kept.into_iter().map(|(entry, is_custom, _)| magic_info(entry, is_custom, bytes)).collect()
+    }
+
+    /// Every custom entry matching `bytes`, then every matching built-in, each
+    /// ordered by longest magic, then largest offset, then table order.
+    fn matching_entries(&self, bytes: &[u8]) -> Vec<(&MagicEntry, bool)> {
+        let mut results = Vec::new();
+
+        for (entries, is_custom) in [(self.entries.as_slice(), true), (MAGIC_NUMBERS, false)] {
+            let mut matches: Vec<&MagicEntry> = entries
+                .iter()
+                .filter(|entry| entry_matches(entry, bytes, bytes))
+                .collect();
+            // Stable, so equally specific entries keep table order
+            matches.sort_by_key(|entry| Reverse((entry.magic.len(), entry.offset)));
+            results.extend(matches.into_iter().map(|entry| (entry, is_custom)));
+        }
+
+        results
+    }
+}
+
+/// Whether every input matching `specific` also matches `general`: both are
+/// anchored the same way and `general`'s magic appears in `specific`'s at the
+/// same position.
+fn covers(general: &MagicEntry, specific: &MagicEntry) -> bool {
+    if general.search_window.is_some() || specific.search_window.is_some() || general.from_end != specific.from_end {
+        return false;
+    }
+
+    // Positions relative to the start, or for `from_end` entries to the end
+    let start = |entry: &MagicEntry| if entry.from_end { -(entry.offset as i128) } else { entry.offset as i128 };
+    let Ok(skip) = usize::try_from(start(general) - start(specific)) else { return false };
+    let Some(end) = skip.checked_add(general.magic.len()) else { return false };
+
+    specific.magic.get(skip..end) == Some(general.magic)
+}
+
+fn conflict(general: &MagicEntry, specific: &MagicEntry, specific_is_builtin: bool) -> MagicConflict {
+    MagicConflict {
+        general: general.clone(),
+        specific: specific.clone(),
+        specific_is_builtin,
+    }
+}
+
+/// Whether two entries match exactly the same inputs at the same position.
+fn same_signature(a: &MagicEntry, b: &MagicEntry) -> bool {
+    (a.offset, a.magic, a.from_end, a.search_window) == (b.offset, b.magic, b.from_end, b.search_window)
+}
+
+/// Absolute range of `bytes` matched by `entry`'s magic.
+fn match_range(entry: &MagicEntry, bytes: &[u8]) -> Option<Range<usize>> {
+    let offset = match_offset(entry, bytes, bytes)?;
+    let start = if entry.from_end { bytes.len() - offset } else { offset };
+    Some(start..start + entry.magic.len())
+}
+
+/// Empty input, reported explicitly rather than as unknown.
+pub(crate) fn empty_match(bytes: &[u8]) -> Option<(FileInfo, DetectionDetail)> {
+    bytes.is_empty().then(|| {
+        (
+            detected(EMPTY_DESCRIPTION.to_string(), None, FileType::Empty, Confidence::Exact, DetectionSource::Empty, bytes),
+            DetectionDetail::without_signature(DetectionSource::Empty),
+        )
+    })
+}
+
+/// The `infer` fallback.
+pub(crate) fn infer_match(bytes: &[u8]) -> Option<(FileInfo, DetectionDetail)> {
+    let kind = infer::get(bytes)?;
+    Some((infer_info(&kind, bytes), DetectionDetail::without_signature(DetectionSource::Infer)))
+}
+
+/// Plain text, recognized so it isn't reported as unknown.
+/// XML documents without a leading XML declaration signature, such as a bare
+/// `<svg>`, are refined into their dialect.
+pub(crate) fn text_match(bytes: &[u8]) -> Option<(FileInfo, DetectionDetail)> {
+    let kind = classify_text(bytes).filter(|kind| *kind != TextKind::Binary)?;
+    let info = match xml_kind(bytes) {
+        Some((description, mime)) => detected(
+            description.to_string(),
+            Some(mime.to_string()),
+            FileType::detected(Some(mime), description),
+            Confidence::Probable,
+            DetectionSource::Text,
+            bytes,
+        ),
+        None => detected(
+            kind.description().to_string(),
+            Some("text/plain".to_string()),
+            FileType::PlainText,
+            Confidence::Probable,
+            DetectionSource::Text,
+            bytes,
+        ),
+    };
+    Some((info, DetectionDetail::without_signature(DetectionSource::Text)))
+}
+
+/// A FileInfo for `bytes` as detected, with no path yet.
+fn detected(
+    description: String,
+    mime_type: Option<String>,
+    file_type: FileType,
+    confidence: Confidence,
+    source: DetectionSource,
+    bytes: &[u8],
+) -> FileInfo {
+    FileInfo {
+        path: PathBuf::new(),
+        description,
+        file_type,
+        mime_type,
+        is_directory: false,
+        size: Some(bytes.len() as u64),
+        confidence,
+        modified: None,
+        created: None,
+        is_executable: None,
+        source,
+    }
+}
+
+/// The FileInfo for a magic entry matching `bytes`.
+fn magic_info(entry: &MagicEntry, is_custom: bool, bytes: &[u8]) -> FileInfo {
+    let (description, mime) = refine_match(entry, bytes);
+    let file_type = if is_custom {
+        FileType::Custom(description.clone())
+    } else {
+        FileType::detected(mime, &description)
+    };
+    detected(description, mime.map(str::to_string), file_type, Confidence::Exact, DetectionSource::CustomMagic, bytes)
+}
+
+/// The FileInfo for an `infer` match on `bytes`.
+fn infer_info(kind: &infer::Type, bytes: &[u8]) -> FileInfo {
+    let description = describe_infer_type(kind);
+    let file_type = FileType::detected(Some(kind.mime_type()), &description);
+    detected(description, Some(kind.mime_type().to_string()), file_type, Confidence::Probable, DetectionSource::Infer, bytes)
+}
+
+/// Description and MIME type for a magic match, refined for generic container
+/// formats and scripts whose contents reveal a more specific type.
+fn refine_match(entry: &MagicEntry, bytes: &[u8]) -> (String, Option<&'static str>) {
+    let refined = match entry.mime {
+        Some("application/zip") => zip_kind(bytes).map(|(description, mime)| (description.to_string(), mime)),
+        Some("application/x-ole-storage") => cfb_kind(bytes).map(|(description, mime)| (description.to_string(), mime)),
+        Some("text/xml") => xml_kind(bytes).map(|(description, mime)| (description.to_string(), Some(mime))),
+        // `<!DOCTYPE html` also starts XHTML documents
+        Some("text/html") => xml_kind(bytes)
+            .filter(|(_, mime)| *mime == "application/xhtml+xml")
+            .map(|(description, mime)| (description.to_string(), Some(mime))),
+        Some("text/x-shellscript") => shebang_kind(bytes).map(|(description, mime)| (description, mime.or(entry.mime))),
+        _ => None,
+    };
+    refined.unwrap_or_else(|| (entry.description.to_string(), entry.mime))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+    fn entry(offset: usize, magic: &'static [u8], description: &'static str) -> MagicEntry {
+        MagicEntry { offset, magic, description, mime: None, from_end: false, search_window: None }
+    }
+
+    #[test]
+    fn custom_entry_matches_alongside_builtins() {
+        let mut registry = MagicRegistry::new();
+        registry.register(entry(0, b"UFC1", "Custom test format"));
+
+        let info = registry.identify_from_bytes_with(b"UFC1 payload").unwrap();
+        assert_eq!(info.description, "Custom test format");
+        assert_eq!(info.file_type, FileType::Custom("Custom test format".to_string()));
+
+        let info = registry.identify_from_bytes_with(PNG).unwrap();
+        assert_eq!(info.mime_type.as_deref(), Some("image/png"));
+    }
+
+    #[test]
+    fn custom_entry_is_checked_before_builtins() {
+        let mut registry = MagicRegistry::new();
+        registry.register(entry(0, b"\x89PNG", "Not really a PNG"));
+        assert_eq!(registry.identify_from_bytes_with(PNG).unwrap().description, "Not really a PNG");
+    }
+
+    #[test]
+    fn from_end_entry_matches_suffix() {
+        let mut registry = MagicRegistry::new();
+        registry.register(MagicEntry { from_end: true, ..entry(4, b"TAIL", "Trailer format") });
+
+        assert_eq!(registry.identify_from_bytes_with(b"\x01\x02 payload TAIL").unwrap().description, "Trailer format");
+        assert_ne!(
+            registry.identify_from_bytes_with(b"\x01\x02TAIL payload").map(|info| info.description),
+            Some("Trailer format".to_string())
+        );
+    }
+
+    #[test]
+    fn seekable_reader_matches_trailer_past_the_header() {
+        let mut image = vec![0x01; crate::header_read_len() + 4096];
+        let trailer = image.len() - 512;
+        image[trailer..trailer + 4].copy_from_slice(b"koly");
+
+        let info = crate::identify_from_seekable(&mut std::io::Cursor::new(&image)).unwrap().unwrap();
+        assert_eq!(info.mime_type.as_deref(), Some("application/x-apple-diskimage"));
+    }
+
+    #[test]
+    fn all_matches_are_ordered_by_specificity() {
+        let mut registry = MagicRegistry::new();
+        registry.register(entry(0, b"RI", "Short custom"));
+        registry.register(entry(8, b"WAVE", "Offset custom"));
+        registry.register(entry(0, b"RIFF", "Long custom"));
+
+        let bytes = b"RIFF\x24\x00\x00\x00WAVEfmt ";
+        let descriptions: Vec<String> = registry
+            .identify_all_matches(bytes)
+            .into_iter()
+            .map(|info| info.description)
+            .collect();
+        assert_eq!(
+            descriptions,
+            [
+                "Offset custom",
+                "Long custom",
+                "Short custom",
+                "WAVE audio file (within RIFF/RIFX)",
+                "RIFF container (AVI, WAV, WebP)",
+            ]
+        );
+    }
+
+    #[test]
+    fn matched_bytes_reflects_signature_length() {
+        let jfif = b"\xFF\xD8\xFF\xE0\x00\x10JFIF\x00\x01";
+        let (_, long) = MagicRegistry::new().identify_detailed(jfif).unwrap();
+        assert_eq!(long.matched_bytes, 10);
+
+        let mut registry = MagicRegistry::new();
+        registry.register(entry(0, b"\xFF\xD8", "Short JPEG signature"));
+        let (info, short) = registry.identify_detailed(jfif).unwrap();
+        assert_eq!(info.description, "Short JPEG signature");
+        assert_eq!(short.matched_bytes, 2);
+        assert!(long.matched_bytes > short.matched_bytes);
+    }
+
+    #[test]
+    fn validate_reports_shadowing_entries() {
+        let mut registry = MagicRegistry::new();
+        registry.register(entry(0, b"UFC", "Short custom format"));
+        registry.register(entry(0, b"UFC1", "Long custom format"));
+        registry.register(entry(2, b"C1", "Inner custom format"));
+
+        let conflicts = registry.validate();
+        let pairs: Vec<_> = conflicts
+            .iter()
+            .map(|conflict| (conflict.general.description, conflict.specific.description, conflict.specific_is_builtin))
+            .collect();
+        assert_eq!(
+            pairs,
+            [
+                ("Short custom format", "Long custom format", false),
+                ("Inner custom format", "Long custom format", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_reports_hidden_builtin() {
+        let mut registry = MagicRegistry::new();
+        registry.register(entry(0, b"\x89PN", "PNG prefix"));
+
+        let conflicts = registry.validate();
+        assert!(!conflicts.is_empty());
+        assert!(conflicts.iter().all(|conflict| conflict.specific_is_builtin));
+    }
+
+    #[test]
+    fn validate_handles_extreme_offsets() {
+        let mut registry = MagicRegistry::new();
+        registry.register(entry(usize::MAX, b"END", "Far offset"));
+        registry.register(entry(0, b"ENDS", "Start offset"));
+        assert!(registry.validate().is_empty());
+    }
+
+    /// Registries defining "UFC1" differently, each with an entry of its own.
+    fn conflicting_registries() -> (MagicRegistry, MagicRegistry) {
+        let mut existing = MagicRegistry::new();
+        existing.register(entry(0, b"UFC1", "Existing format"));
+        existing.register(entry(0, b"OWN1", "Existing only"));
+        let mut incoming = MagicRegistry::new();
+        incoming.register(entry(0, b"UFC1", "Incoming format"));
+        incoming.register(entry(0, b"OWN1", "Existing only"));
+        incoming.register(entry(0, b"NEW1", "Incoming only"));
+        (existing, incoming)
+    }
+
+    fn descriptions(registry: &MagicRegistry) -> Vec<&str> {
+        registry.entries().iter().map(|entry| entry.description).collect()
+    }
+
+    #[test]
+    fn merge_prefer_existing() {
+        let (mut registry, incoming) = conflicting_registries();
+        registry.merge(incoming, MergePolicy::PreferExisting).unwrap();
+        assert_eq!(descriptions(&registry), ["Existing format", "Existing only", "Incoming only"]);
+    }
+
+    #[test]
+    fn merge_prefer_incoming() {
+        let (mut registry, incoming) = conflicting_registries();
+        registry.merge(incoming, MergePolicy::PreferIncoming).unwrap();
+        assert_eq!(descriptions(&registry), ["Incoming format", "Existing only", "Incoming only"]);
+        assert_eq!(registry.identify_from_bytes_with(b"UFC1 data").unwrap().description, "Incoming format");
+    }
+
+    #[test]
+    fn merge_error_leaves_registry_unchanged() {
+        let (mut registry, incoming) = conflicting_registries();
+        let conflict = registry.merge(incoming, MergePolicy::Error).unwrap_err();
+        assert_eq!((conflict.magic, conflict.offset), (&b"UFC1"[..], 0));
+        assert_eq!((conflict.existing_description, conflict.incoming_description), ("Existing format", "Incoming format"));
+        assert_eq!(descriptions(&registry), ["Existing format", "Existing only"]);
+    }
+
+    #[test]
+    fn merge_skips_identical_entries() {
+        let mut registry = MagicRegistry::new();
+        registry.register(entry(0, b"UFC1", "Same"));
+        let mut incoming = MagicRegistry::new();
+        incoming.register(entry(0, b"UFC1", "Same"));
+        registry.merge(incoming, MergePolicy::Error).unwrap();
+        assert_eq!(descriptions(&registry), ["Same"]);
+    }
+}
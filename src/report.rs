@@ -0,0 +1,151 @@
+//! JSON reports for batches of results, enabled by the `serde` feature.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Category, FileInfo};
+
+/// Summary document produced by `to_json_report`.
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    total_count: usize,
+    total_bytes: u64,
+    categories: BTreeMap<Category, usize>,
+    files: &'a [FileInfo],
+}
+
+/// Build a JSON report for a batch of results.
+/// The report contains the total entry count, the total size of all files
+/// (directories contribute zero bytes), a per-category count of files, and
+/// the entries themselves. Set `pretty` for indented output.
+pub fn to_json_report(results: &[FileInfo], pretty: bool) -> String {
+    let files = results.iter().filter(|info| !info.is_directory);
+
+    let mut categories = BTreeMap::new();
+    for info in files.clone() {
+        *categories.entry(info.category()).or_insert(0) += 1;
+    }
+
+    let report = JsonReport {
+        total_count: results.len(),
+        total_bytes: files.filter_map(|info| info.size).sum(),
+        categories,
+        files: results,
+    };
+
+    // Paths are serialized lossily, times out of range as null, and map keys
+    // are plain enum names, so serialization cannot fail.
+    if pretty {
+        serde_json::to_string_pretty(&report).expect("report serialization is infallible")
+    } else {
+        serde_json::to_string(&report).expect("report serialization is infallible")
+    }
+}
+
+/// Serialize a path as a string, replacing invalid UTF-8 so serialization never fails.
+pub(crate) fn serialize_path_lossy<S: Serializer>(path: &Path, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&path.to_string_lossy())
+}
+
+/// A point in time as whole seconds since the Unix epoch (negative before it)
+/// plus the nanoseconds past that second. Same shape as serde's own
+/// `SystemTime` format, which rejects times before the epoch.
+#[derive(Serialize, Deserialize)]
+struct EpochTime {
+    secs_since_epoch: i64,
+    nanos_since_epoch: u32,
+}
+
+impl EpochTime {
+    fn from_system_time(time: SystemTime) -> Option<Self> {
+        match time.duration_since(UNIX_EPOCH) {
+            Ok(after) => Some(Self {
+                secs_since_epoch: i64::try_from(after.as_secs()).ok()?,
+                nanos_since_epoch: after.subsec_nanos(),
+            }),
+            Err(error) => {
+                let before = error.duration();
+                let secs = i64::try_from(before.as_secs()).ok()?;
+                Some(match before.subsec_nanos() {
+                    0 => Self { secs_since_epoch: -secs, nanos_since_epoch: 0 },
+                    nanos => Self { secs_since_epoch: (-secs).checked_sub(1)?, nanos_since_epoch: 1_000_000_000 - nanos },
+                })
+            }
+        }
+    }
+
+    fn to_system_time(&self) -> Option<SystemTime> {
+        let secs = Duration::from_secs(self.secs_since_epoch.unsigned_abs());
+        let whole = if self.secs_since_epoch < 0 { UNIX_EPOCH.checked_sub(secs) } else { UNIX_EPOCH.checked_add(secs) };
+        whole?.checked_add(Duration::from_nanos(u64::from(self.nanos_since_epoch)))
+    }
+}
+
+/// Serialize a time as an `EpochTime`, or as None if it lies too far from the
+/// epoch to fit, so serialization never fails.
+pub(crate) fn serialize_time<S: Serializer>(time: &Option<SystemTime>, serializer: S) -> Result<S::Ok, S::Error> {
+    time.and_then(EpochTime::from_system_time).serialize(serializer)
+}
+
+/// Deserialize a time written by `serialize_time`.
+pub(crate) fn deserialize_time<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<SystemTime>, D::Error> {
+    Option::<EpochTime>::deserialize(deserializer)?
+        .map(|time| time.to_system_time().ok_or_else(|| D::Error::custom("time out of range for this platform")))
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::path::PathBuf;
+
+    use crate::{identify_from_bytes, identify_multiple};
+
+    #[test]
+    fn totals_sum_file_sizes_and_skip_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut results = identify_multiple(&[dir.path()]).unwrap();
+        for (name, bytes) in [("a.png", &b"\x89PNG\r\n\x1a\n"[..]), ("b.zip", b"PK\x03\x04\x14\x00")] {
+            results.push(FileInfo {
+                path: PathBuf::from(name),
+                ..identify_from_bytes(bytes).unwrap()
+            });
+        }
+
+        for pretty in [false, true] {
+            let report: serde_json::Value = serde_json::from_str(&to_json_report(&results, pretty)).unwrap();
+            assert_eq!(report["total_count"], 3);
+            assert_eq!(report["total_bytes"], 14);
+            assert_eq!(report["categories"]["Image"], 1);
+            assert_eq!(report["categories"]["Archive"], 1);
+            assert!(report["categories"].get("Directory").is_none());
+            assert_eq!(report["files"].as_array().unwrap().len(), 3);
+        }
+    }
+
+    #[test]
+    fn times_before_the_epoch_are_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("old.txt");
+        std::fs::write(&path, "from 1969").unwrap();
+        let modified = UNIX_EPOCH - Duration::new(86_400, 250_000_000);
+        let times = std::fs::FileTimes::new().set_modified(modified);
+        std::fs::File::options().write(true).open(&path).unwrap().set_times(times).unwrap();
+
+        let results = identify_multiple(&[&path]).unwrap();
+        assert_eq!(results[0].modified, Some(modified));
+
+        let report: serde_json::Value = serde_json::from_str(&to_json_report(&results, false)).unwrap();
+        let reported = &report["files"][0]["modified"];
+        assert_eq!(reported["secs_since_epoch"], -86_401);
+        assert_eq!(reported["nanos_since_epoch"], 750_000_000);
+
+        let info: FileInfo = serde_json::from_value(report["files"][0].clone()).unwrap();
+        assert_eq!(info, results[0]);
+    }
+}
@@ -0,0 +1,437 @@
+//! A single configurable entry point for scanning files and directory trees.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use walkdir::{DirEntry, WalkDir};
+
+use crate::traversal::{TraversalOptions, is_hidden};
+use crate::{
+    Category, FileInfo, FileProcessingError, canonicalize_paths, create_symlink_info, header_read_len, identify_file_counted,
+};
+
+/// Configuration for `scan`, combining the options of the various
+/// `identify_recursive_*` functions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanConfig {
+    traversal: TraversalOptions,
+    max_file_bytes: usize,
+    canonicalize: bool,
+    categories: Option<BTreeSet<Category>>,
+    description_mapper: Option<DescriptionMapper>,
+    #[cfg(feature = "parallel")]
+    parallel: bool,
+}
+
+/// A description mapper set with `ScanConfig::description_mapper`. Mappers are
+/// equal only if they are the same closure.
+#[derive(Clone)]
+struct DescriptionMapper(Arc<dyn Fn(&str) -> String + Send + Sync>);
+
+impl fmt::Debug for DescriptionMapper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DescriptionMapper(..)")
+    }
+}
+
+impl PartialEq for DescriptionMapper {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for DescriptionMapper {}
+
+/// Counters from `ScanConfig::scan_with_stats`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScanStats {
+    /// Bytes actually read from files for identification, at most
+    /// `max_file_bytes` plus any trailer per file rather than the file sizes
+    pub bytes_read: u64,
+    /// Entries identified that aren't directories, including symbolic links
+    /// and special files
+    pub files_visited: usize,
+    /// Directories identified, including the roots
+    pub directories_visited: usize,
+    /// Wall-clock time taken by the whole scan
+    pub elapsed: Duration,
+}
+
+/// Results of earlier scans, reused by `ScanConfig::scan_cached` for files
+/// whose modification time and size haven't changed since.
+/// Files are keyed by their path as walked, so a cache only helps scans that
+/// reach the same files through the same roots. Files on platforms that
+/// don't record modification times are never cached.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScanCache {
+    entries: BTreeMap<PathBuf, FileInfo>,
+}
+
+impl ScanCache {
+    /// Create an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of cached files.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no files are cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Forget all cached files.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// The cached result for `entry`, if it is a regular file whose
+    /// modification time and size match the cached ones.
+    fn get(&self, entry: &DirEntry) -> Option<&FileInfo> {
+        let cached = self.entries.get(entry.path())?;
+        let metadata = entry.metadata().ok().filter(|metadata| metadata.is_file())?;
+        let unchanged = cached.modified.is_some()
+            && cached.modified == metadata.modified().ok()
+            && cached.size == Some(metadata.len());
+        unchanged.then_some(cached)
+    }
+
+    /// Remember a freshly identified file. Entries without a modification
+    /// time or size, such as directories and symbolic links, aren't cached.
+    fn insert(&mut self, info: &FileInfo) {
+        if !info.is_directory && info.modified.is_some() && info.size.is_some() {
+            self.entries.insert(info.path.clone(), info.clone());
+        }
+    }
+}
+
+impl Default for ScanConfig {
+    fn default() -> Self {
+        Self {
+            traversal: TraversalOptions::default(),
+            max_file_bytes: header_read_len(),
+            canonicalize: false,
+            categories: None,
+            description_mapper: None,
+            #[cfg(feature = "parallel")]
+            parallel: false,
+        }
+    }
+}
+
+impl ScanConfig {
+    /// Create a configuration with the same behavior as `identify_recursive`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maximum depth to descend below each root; 0 scans only the roots themselves.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.traversal.max_depth = max_depth;
+        self
+    }
+
+    /// Follow symbolic links and identify their targets instead of
+    /// reporting them as "Symbolic link".
+    pub fn follow_links(mut self, follow_links: bool) -> Self {
+        self.traversal.follow_links = follow_links;
+        self
+    }
+
+    /// Whether to include hidden entries; see `TraversalOptions::include_hidden`.
+    pub fn include_hidden(mut self, include_hidden: bool) -> Self {
+        self.traversal.include_hidden = include_hidden;
+        self
+    }
+
+    /// Maximum number of bytes read from each file for identification.
+    /// Defaults to `header_read_len()`; smaller values are faster but may
+    /// miss signatures at larger offsets.
+    pub fn max_file_bytes(mut self, max_file_bytes: usize) -> Self {
+        self.max_file_bytes = max_file_bytes;
+        self
+    }
+
+    /// Report absolute, canonical paths instead of paths relative to the roots
+    /// as given; see `canonicalize_paths`.
+    pub fn canonicalize(mut self, canonicalize: bool) -> Self {
+        self.canonicalize = canonicalize;
+        self
+    }
+
+    /// Only let the `infer` fallback report file types in `categories`, e.g.
+    /// just `Category::Image`, so its broad matchers can't produce false
+    /// positives outside the categories of interest. A file it would have
+    /// labeled as anything else falls through to text classification, and is
+    /// otherwise reported as unknown. The magic number table and text
+    /// classification are unaffected.
+    pub fn categories(mut self, categories: impl IntoIterator<Item = Category>) -> Self {
+        self.categories = Some(categories.into_iter().collect());
+        self
+    }
+
+    /// Rewrite each result's description with `mapper`, e.g. to localize it.
+    /// The MIME type and `file_type` are left untouched.
+    pub fn description_mapper(mut self, mapper: impl Fn(&str) -> String + Send + Sync + 'static) -> Self {
+        self.description_mapper = Some(DescriptionMapper(Arc::new(mapper)));
+        self
+    }
+
+    /// Identify entries on a rayon thread pool. Each root is walked first, then
+    /// its entries are identified in parallel; results keep traversal order.
+    #[cfg(feature = "parallel")]
+    pub fn parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    /// Scan each root in turn, returning results for all roots in order.
+    /// Fails with `PathNotFound` if any root doesn't exist.
+    pub fn scan(&self, roots: &[PathBuf]) -> Result<Vec<FileInfo>, FileProcessingError> {
+        self.scan_with_stats(roots).map(|(results, _)| results)
+    }
+
+    /// Like `scan`, but also reports how much was read and how long it took.
+    pub fn scan_with_stats(&self, roots: &[PathBuf]) -> Result<(Vec<FileInfo>, ScanStats), FileProcessingError> {
+        self.run(roots, None)
+    }
+
+    /// Like `scan`, but reuses results from `cache` for files that haven't
+    /// changed, without reading them, and stores the results for files that
+    /// were read. The cache holds results as identified, before
+    /// canonicalization and description mapping, so it can be shared between
+    /// configurations that differ only in those options.
+    pub fn scan_cached(&self, roots: &[PathBuf], cache: &mut ScanCache) -> Result<Vec<FileInfo>, FileProcessingError> {
+        self.scan_cached_with_stats(roots, cache).map(|(results, _)| results)
+    }
+
+    /// Like `scan_cached`, but also reports how much was read and how long it
+    /// took. Files reused from the cache count as visited with no bytes read.
+    pub fn scan_cached_with_stats(
+        &self,
+        roots: &[PathBuf],
+        cache: &mut ScanCache,
+    ) -> Result<(Vec<FileInfo>, ScanStats), FileProcessingError> {
+        self.run(roots, Some(cache))
+    }
+
+    fn run(&self, roots: &[PathBuf], mut cache: Option<&mut ScanCache>) -> Result<(Vec<FileInfo>, ScanStats), FileProcessingError> {
+        let start = Instant::now();
+        let options = &self.traversal;
+        let mut results = Vec::new();
+        let mut stats = ScanStats::default();
+
+        for root in roots {
+            if !root.exists() {
+                return Err(FileProcessingError::PathNotFound(root.clone()));
+            }
+
+            let entries = WalkDir::new(root)
+                .max_depth(options.max_depth)
+                .follow_links(options.follow_links)
+                .into_iter()
+                .filter_entry(|entry| options.include_hidden || entry.depth() == 0 || !is_hidden(entry))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            for (file_info, bytes_read) in self.identify_entries(&entries, cache.as_deref())? {
+                stats.bytes_read += bytes_read;
+                if let Some(cache) = cache.as_deref_mut()
+                    && bytes_read > 0
+                {
+                    cache.insert(&file_info);
+                }
+                if file_info.is_directory {
+                    stats.directories_visited += 1;
+                } else {
+                    stats.files_visited += 1;
+                }
+                results.push(file_info);
+            }
+        }
+
+        if self.canonicalize {
+            results = canonicalize_paths(results);
+        }
+        if let Some(DescriptionMapper(mapper)) = &self.description_mapper {
+            for info in &mut results {
+                info.description = mapper(&info.description);
+            }
+        }
+
+        stats.elapsed = start.elapsed();
+        Ok((results, stats))
+    }
+
+    fn identify_entries(&self, entries: &[DirEntry], cache: Option<&ScanCache>) -> Result<Vec<(FileInfo, u64)>, FileProcessingError> {
+        #[cfg(feature = "parallel")]
+        if self.parallel {
+            return entries.par_iter().map(|entry| self.identify_entry(entry, cache)).collect();
+        }
+
+        entries.iter().map(|entry| self.identify_entry(entry, cache)).collect()
+    }
+
+    /// Identify an entry, along with the number of bytes read to do so.
+    fn identify_entry(&self, entry: &DirEntry, cache: Option<&ScanCache>) -> Result<(FileInfo, u64), FileProcessingError> {
+        if entry.path_is_symlink() && !self.traversal.follow_links {
+            return Ok((create_symlink_info(entry.path()), 0));
+        }
+        if let Some(cached) = cache.and_then(|cache| cache.get(entry)) {
+            return Ok((cached.clone(), 0));
+        }
+        identify_file_counted(entry.path(), self.max_file_bytes, self.categories.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+    use std::path::Path;
+
+    use crate::DetectionSource;
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+    /// A tree with a hidden file and directory at the top level and files
+    /// one and two levels down.
+    fn tree() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("sub/deeper")).unwrap();
+        fs::create_dir(root.join(".git")).unwrap();
+        fs::write(root.join("top.png"), PNG).unwrap();
+        fs::write(root.join(".hidden"), b"secret").unwrap();
+        fs::write(root.join(".git/config"), b"[core]").unwrap();
+        fs::write(root.join("sub/middle.png"), PNG).unwrap();
+        fs::write(root.join("sub/deeper/bottom.png"), PNG).unwrap();
+        dir
+    }
+
+    fn relative_paths(root: &Path, results: &[FileInfo]) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = results
+            .iter()
+            .map(|info| info.path.strip_prefix(root).unwrap().to_path_buf())
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn depth_limit_and_hidden_exclusion_combine() {
+        let dir = tree();
+        let config = ScanConfig::new().max_depth(1).include_hidden(false);
+        let results = config.scan(&[dir.path().to_path_buf()]).unwrap();
+
+        assert_eq!(
+            relative_paths(dir.path(), &results),
+            [PathBuf::new(), PathBuf::from("sub"), PathBuf::from("top.png")]
+        );
+    }
+
+    #[test]
+    fn default_config_matches_identify_recursive() {
+        let dir = tree();
+        let results = ScanConfig::new().scan(&[dir.path().to_path_buf()]).unwrap();
+        assert_eq!(results, crate::identify_recursive(dir.path()).unwrap());
+    }
+
+    #[test]
+    fn stats_count_visits_and_bounded_reads() {
+        let dir = tree();
+        let large = vec![b'a'; 1 << 20];
+        fs::write(dir.path().join("large.txt"), &large).unwrap();
+        let (results, stats) = ScanConfig::new().scan_with_stats(&[dir.path().to_path_buf()]).unwrap();
+
+        let files = results.iter().filter(|info| !info.is_directory).count();
+        assert_eq!(stats.files_visited, files);
+        assert_eq!(stats.files_visited + stats.directories_visited, results.len());
+        assert!(stats.bytes_read > 0);
+        assert!(stats.bytes_read < large.len() as u64);
+    }
+
+    #[test]
+    fn description_mapper_applies_to_every_result() {
+        let dir = tree();
+        let plain = ScanConfig::new().scan(&[dir.path().to_path_buf()]).unwrap();
+        let mapped = ScanConfig::new()
+            .description_mapper(|description| description.to_uppercase())
+            .scan(&[dir.path().to_path_buf()])
+            .unwrap();
+
+        assert_eq!(mapped.len(), plain.len());
+        for (plain, mapped) in plain.iter().zip(&mapped) {
+            assert_eq!(mapped.description, plain.description.to_uppercase());
+            assert_eq!(mapped.mime_type, plain.mime_type);
+            assert_eq!(mapped.file_type, plain.file_type);
+        }
+    }
+
+    #[test]
+    fn second_cached_scan_reads_nothing() {
+        let dir = tree();
+        let roots = [dir.path().to_path_buf()];
+        let config = ScanConfig::new();
+        let mut cache = ScanCache::new();
+
+        let (first, first_stats) = config.scan_cached_with_stats(&roots, &mut cache).unwrap();
+        assert!(first_stats.bytes_read > 0);
+        assert!(!cache.is_empty());
+
+        let (second, second_stats) = config.scan_cached_with_stats(&roots, &mut cache).unwrap();
+        assert_eq!(second_stats.bytes_read, 0);
+        assert_eq!(second_stats.files_visited, first_stats.files_visited);
+        assert_eq!(second, first);
+
+        fs::write(dir.path().join("top.png"), b"now plain text\n").unwrap();
+        let (third, third_stats) = config.scan_cached_with_stats(&roots, &mut cache).unwrap();
+        assert!(third_stats.bytes_read > 0);
+        let top = third.iter().find(|info| info.path.ends_with("top.png")).unwrap();
+        assert_eq!(top.mime_type.as_deref(), Some("text/plain"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn cache_round_trips_through_serde() {
+        let dir = tree();
+        let mut cache = ScanCache::new();
+        ScanConfig::new().scan_cached(&[dir.path().to_path_buf()], &mut cache).unwrap();
+
+        let json = serde_json::to_string(&cache).unwrap();
+        assert_eq!(serde_json::from_str::<ScanCache>(&json).unwrap(), cache);
+    }
+
+    #[test]
+    fn category_restriction_only_applies_to_infer() {
+        let dir = tempfile::tempdir().unwrap();
+        let wasm = b"\0asm\x01\0\0\0";
+        fs::write(dir.path().join("image.png"), PNG).unwrap();
+        fs::write(dir.path().join("archive.zip"), b"PK\x05\x06\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0").unwrap();
+        fs::write(dir.path().join("module.wasm"), wasm).unwrap();
+        fs::write(dir.path().join("notes.txt"), "plain text").unwrap();
+        assert_eq!(crate::identify_from_bytes(wasm).unwrap().source, DetectionSource::Infer);
+
+        let results = ScanConfig::new()
+            .categories([Category::Image])
+            .scan(&[dir.path().to_path_buf()])
+            .unwrap();
+        let find = |name: &str| results.iter().find(|info| info.path.ends_with(name)).unwrap();
+
+        assert_eq!(find("image.png").mime_type.as_deref(), Some("image/png"));
+        assert_eq!(find("archive.zip").mime_type.as_deref(), Some("application/zip"));
+        assert_eq!(find("notes.txt").mime_type.as_deref(), Some("text/plain"));
+        assert!(find("module.wasm").is_unknown());
+        assert_eq!(find("module.wasm").mime_type, None);
+        assert!(results.iter().any(|info| info.is_directory));
+    }
+}
@@ -0,0 +1,99 @@
+//! Identification of scripts by their `#!` interpreter line.
+
+/// Script type for each known interpreter, keyed by interpreter name with any
+/// version suffix removed.
+const INTERPRETERS: &[(&str, &str, &str)] = &[
+    ("sh", "Shell script", "text/x-shellscript"),
+    ("bash", "Shell script", "text/x-shellscript"),
+    ("dash", "Shell script", "text/x-shellscript"),
+    ("ash", "Shell script", "text/x-shellscript"),
+    ("ksh", "Shell script", "text/x-shellscript"),
+    ("zsh", "Shell script", "text/x-shellscript"),
+    ("csh", "Shell script", "text/x-shellscript"),
+    ("tcsh", "Shell script", "text/x-shellscript"),
+    ("fish", "Shell script", "text/x-shellscript"),
+    ("python", "Python script", "text/x-python"),
+    ("perl", "Perl script", "text/x-perl"),
+    ("ruby", "Ruby script", "text/x-ruby"),
+    ("node", "Node.js script", "text/javascript"),
+    ("nodejs", "Node.js script", "text/javascript"),
+    ("php", "PHP script", "application/x-httpd-php"),
+    ("lua", "Lua script", "text/x-lua"),
+    ("tclsh", "Tcl script", "text/x-tcl"),
+    ("wish", "Tcl script", "text/x-tcl"),
+    ("awk", "awk script", "text/x-awk"),
+    ("gawk", "awk script", "text/x-awk"),
+    ("Rscript", "R script", "text/x-r"),
+];
+
+/// Describe a script by the interpreter named on its `#!` line, e.g.
+/// "Python script" for `#!/usr/bin/env python3`. Interpreters without a
+/// known name are described as "<name> script".
+/// Returns None if the input doesn't start with a shebang naming an interpreter.
+pub fn detect_shebang(bytes: &[u8]) -> Option<String> {
+    shebang_kind(bytes).map(|(description, _)| description)
+}
+
+/// Description and MIME type (if known) of a script from its `#!` line.
+pub(crate) fn shebang_kind(bytes: &[u8]) -> Option<(String, Option<&'static str>)> {
+    let name = interpreter_name(bytes)?;
+    let base = name.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+
+    Some(match INTERPRETERS.iter().find(|(known, _, _)| *known == base) {
+        Some((_, description, mime)) => (description.to_string(), Some(*mime)),
+        None => (format!("{name} script"), None),
+    })
+}
+
+/// Basename of the interpreter on the `#!` line, looking through `env`.
+fn interpreter_name(bytes: &[u8]) -> Option<String> {
+    let rest = bytes.strip_prefix(b"#!")?;
+    let line = match rest.iter().position(|&byte| byte == b'\n') {
+        Some(end) => &rest[..end],
+        None => rest,
+    };
+    let line = String::from_utf8_lossy(line);
+
+    let mut words = line.split_whitespace();
+    let mut name = basename(words.next()?);
+
+    // `#!/usr/bin/env [-S] [VAR=value ...] interpreter`
+    if name == "env" {
+        name = basename(words.find(|word| !word.starts_with('-') && !word.contains('='))?);
+    }
+
+    Some(name.to_string())
+}
+
+fn basename(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::identify_from_bytes;
+
+    #[test]
+    fn shell_script() {
+        assert_eq!(detect_shebang(b"#!/bin/sh\necho hi\n").as_deref(), Some("Shell script"));
+        let info = identify_from_bytes(b"#!/bin/sh\necho hi\n").unwrap();
+        assert_eq!(info.description, "Shell script");
+        assert_eq!(info.mime_type.as_deref(), Some("text/x-shellscript"));
+    }
+
+    #[test]
+    fn env_python_with_crlf_and_without_newline() {
+        assert_eq!(detect_shebang(b"#!/usr/bin/env python3\r\nprint(1)\r\n").as_deref(), Some("Python script"));
+        assert_eq!(detect_shebang(b"#!/usr/bin/env python3").as_deref(), Some("Python script"));
+        let info = identify_from_bytes(b"#!/usr/bin/env python3\nprint(1)\n").unwrap();
+        assert_eq!(info.mime_type.as_deref(), Some("text/x-python"));
+    }
+
+    #[test]
+    fn no_shebang() {
+        assert_eq!(detect_shebang(b"echo hi\n"), None);
+        assert_eq!(detect_shebang(b"#!"), None);
+    }
+}
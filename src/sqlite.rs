@@ -0,0 +1,57 @@
+//! Details of SQLite databases beyond their type.
+
+const SQLITE_MAGIC: &[u8] = b"SQLite format 3\0";
+
+/// Header fields of a SQLite database, from `sqlite_info`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SqliteInfo {
+    /// Database page size in bytes, a power of two from 512 to 65536
+    pub page_size: u32,
+    /// Schema format number, 1 to 4
+    pub schema_format: u32,
+}
+
+/// Parse the page size and schema format number from the 100-byte header of a
+/// SQLite 3 database.
+/// Returns None if the input isn't a SQLite database or the header is truncated.
+pub fn sqlite_info(bytes: &[u8]) -> Option<SqliteInfo> {
+    if !bytes.starts_with(SQLITE_MAGIC) {
+        return None;
+    }
+
+    // A stored page size of 1 stands for 65536, which doesn't fit the field
+    let page_size = match u16::from_be_bytes(bytes.get(16..18)?.try_into().ok()?) {
+        1 => 65536,
+        size => u32::from(size),
+    };
+    let schema_format = u32::from_be_bytes(bytes.get(44..48)?.try_into().ok()?);
+
+    Some(SqliteInfo { page_size, schema_format })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(page_size: u16) -> [u8; 100] {
+        let mut header = [0; 100];
+        header[..16].copy_from_slice(SQLITE_MAGIC);
+        header[16..18].copy_from_slice(&page_size.to_be_bytes());
+        header[44..48].copy_from_slice(&4u32.to_be_bytes());
+        header
+    }
+
+    #[test]
+    fn page_size_and_schema_format() {
+        assert_eq!(sqlite_info(&header(4096)), Some(SqliteInfo { page_size: 4096, schema_format: 4 }));
+        assert_eq!(sqlite_info(&header(1)).unwrap().page_size, 65536);
+        assert_eq!(crate::identify_from_bytes(&header(4096)).unwrap().mime_type.as_deref(), Some("application/vnd.sqlite3"));
+    }
+
+    #[test]
+    fn non_sqlite_or_truncated_input() {
+        assert_eq!(sqlite_info(b"\x89PNG\r\n\x1a\n"), None);
+        assert_eq!(sqlite_info(&header(4096)[..40]), None);
+    }
+}
@@ -0,0 +1,90 @@
+//! Identification of files made of several concatenated streams, such as
+//! multi-member gzip files.
+
+use crate::{FileInfo, identify_bytes_or_unknown};
+
+/// Upper bound on the number of streams returned by `identify_streams`.
+pub const MAX_STREAMS: usize = 4096;
+
+/// Signatures that start a self-contained stream and are distinctive enough
+/// to search for anywhere in the input, not only at its start.
+const STREAM_SIGNATURES: &[&[u8]] = &[
+    // gzip member using deflate, the only compression method defined
+    &[0x1F, 0x8B, 0x08],
+    // bzip2 stream: "BZh" and the block size, checked in `plausible_start`
+    b"BZh",
+    &[0xFD, 0x37, 0x7A, 0x58, 0x5A, 0x00],
+    &[0x28, 0xB5, 0x2F, 0xFD],
+    &[0x04, 0x22, 0x4D, 0x18],
+    b"%PDF-",
+    b"\x89PNG\r\n\x1a\n",
+];
+
+/// Identify each stream in `bytes` that starts with a known stream signature,
+/// such as every member of a multi-member gzip file or every document in
+/// concatenated PDFs. Returns each stream's offset with its type; a stream's
+/// `size` runs up to the next stream or the end of the input.
+/// Data before the first signature isn't reported, and at most `MAX_STREAMS`
+/// streams are returned. The scan is linear in the input length.
+pub fn identify_streams(bytes: &[u8]) -> Vec<(usize, FileInfo)> {
+    let offsets: Vec<usize> = (0..bytes.len())
+        .filter(|&offset| plausible_start(&bytes[offset..]))
+        .take(MAX_STREAMS)
+        .collect();
+
+    offsets
+        .iter()
+        .enumerate()
+        .map(|(index, &offset)| {
+            let end = offsets.get(index + 1).copied().unwrap_or(bytes.len());
+            let mut info = identify_bytes_or_unknown(&bytes[offset..]);
+            info.size = Some((end - offset) as u64);
+            (offset, info)
+        })
+        .collect()
+}
+
+/// Whether a stream signature starts `bytes`, with the header fields that
+/// follow short signatures checked to avoid matches inside compressed data.
+fn plausible_start(bytes: &[u8]) -> bool {
+    let Some(signature) = STREAM_SIGNATURES.iter().find(|signature| bytes.starts_with(signature)) else {
+        return false;
+    };
+
+    match *signature {
+        // The top three gzip flag bits are reserved and must be zero
+        [0x1F, 0x8B, 0x08] => bytes.get(3).is_some_and(|flags| flags & 0xE0 == 0),
+        b"BZh" => bytes.get(3).is_some_and(|level| (b'1'..=b'9').contains(level)),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A gzip member holding an empty deflate stream, with a zero CRC and size
+    const GZIP_MEMBER: &[u8] = b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x00\x03\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00";
+
+    #[test]
+    fn concatenated_gzip_members() {
+        let bytes = [GZIP_MEMBER, GZIP_MEMBER].concat();
+        let streams = identify_streams(&bytes);
+
+        let offsets: Vec<usize> = streams.iter().map(|(offset, _)| *offset).collect();
+        assert_eq!(offsets, [0, GZIP_MEMBER.len()]);
+        for (_, info) in &streams {
+            assert_eq!(info.mime_type.as_deref(), Some("application/gzip"));
+            assert_eq!(info.size, Some(GZIP_MEMBER.len() as u64));
+        }
+    }
+
+    #[test]
+    fn leading_data_is_not_reported() {
+        let bytes = [b"junk before".as_slice(), GZIP_MEMBER].concat();
+        let streams = identify_streams(&bytes);
+        assert_eq!(streams.len(), 1);
+        assert_eq!(streams[0].0, 11);
+        assert!(identify_streams(b"no streams here").is_empty());
+    }
+}
@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use crate::FileInfo;
+
+/// Aggregate statistics over a set of results.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScanSummary {
+    /// Number of non-directory entries
+    pub total_files: usize,
+    /// Number of directories
+    pub total_directories: usize,
+    /// Combined size of all files in bytes (directories are excluded)
+    pub total_bytes: u64,
+    /// The largest file, or None if there are no files with a known size.
+    /// The first one encountered wins ties.
+    pub largest_file: Option<FileInfo>,
+    /// Number of files per type description (directories are excluded)
+    pub type_counts: HashMap<String, usize>,
+}
+
+/// Compute aggregate statistics for a set of results.
+pub fn summarize(results: &[FileInfo]) -> ScanSummary {
+    let mut summary = ScanSummary::default();
+    let mut largest: Option<&FileInfo> = None;
+
+    for info in results {
+        if info.is_directory {
+            summary.total_directories += 1;
+            continue;
+        }
+
+        summary.total_files += 1;
+        *summary.type_counts.entry(info.description.clone()).or_insert(0) += 1;
+
+        if let Some(size) = info.size {
+            summary.total_bytes += size;
+            if largest.is_none_or(|current| size > current.size.unwrap_or(0)) {
+                largest = Some(info);
+            }
+        }
+    }
+
+    summary.largest_file = largest.cloned();
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{create_directory_info, create_unknown_info};
+
+    fn file(path: &str, description: &str, size: u64) -> FileInfo {
+        FileInfo {
+            description: description.to_string(),
+            ..create_unknown_info(path, size)
+        }
+    }
+
+    #[test]
+    fn counts_files_bytes_and_types() {
+        let results = [
+            create_directory_info("root"),
+            file("root/a.png", "PNG image", 100),
+            file("root/b.png", "PNG image", 300),
+            file("root/c.zip", "Zip archive", 200),
+            create_directory_info("root/sub"),
+        ];
+
+        let summary = summarize(&results);
+        assert_eq!(summary.total_files, 3);
+        assert_eq!(summary.total_directories, 2);
+        assert_eq!(summary.total_bytes, 600);
+        assert_eq!(summary.largest_file.unwrap().path.to_str(), Some("root/b.png"));
+        assert_eq!(summary.type_counts["PNG image"], 2);
+        assert_eq!(summary.type_counts["Zip archive"], 1);
+        assert!(!summary.type_counts.contains_key("Directory"));
+    }
+
+    #[test]
+    fn directories_only_have_zero_bytes() {
+        let summary = summarize(&[create_directory_info("a"), create_directory_info("b")]);
+        assert_eq!(summary.total_files, 0);
+        assert_eq!(summary.total_directories, 2);
+        assert_eq!(summary.total_bytes, 0);
+        assert_eq!(summary.largest_file, None);
+    }
+}
@@ -0,0 +1,85 @@
+//! Introspection of the formats this crate can detect, for listing them.
+
+use crate::extension_map::{EXTENSION_MAP, extension_for_mime};
+use crate::magicnums::MAGIC_NUMBERS;
+
+/// A format in the built-in magic number table, from `supported_formats`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FormatSpec {
+    /// The description reported for matching files
+    pub description: &'static str,
+    /// The MIME type reported for matching files, if any
+    pub mime: Option<&'static str>,
+    /// Common file extensions for the format, without the leading dot, most
+    /// common first. Empty if none are known
+    pub extensions: Vec<&'static str>,
+    /// Whether the `infer` fallback also recognizes the MIME type
+    pub infer_supported: bool,
+}
+
+/// Every format in the built-in magic number table, in table order, with
+/// entries sharing a description and MIME type listed once.
+/// Types only recognized by the `infer` fallback aren't listed, as `infer`
+/// doesn't expose its table; `infer_supported` marks the overlap instead.
+pub fn supported_formats() -> Vec<FormatSpec> {
+    let mut formats: Vec<FormatSpec> = Vec::new();
+
+    for entry in MAGIC_NUMBERS {
+        if formats
+            .iter()
+            .any(|format| format.description == entry.description && format.mime == entry.mime)
+        {
+            continue;
+        }
+
+        let mut extensions: Vec<&'static str> = Vec::new();
+        if let Some(mime) = entry.mime {
+            let mapped = EXTENSION_MAP
+                .iter()
+                .filter(|mapped| mapped.mime == Some(mime))
+                .map(|mapped| mapped.extension);
+            for extension in extension_for_mime(mime).into_iter().chain(mapped) {
+                if !extensions.contains(&extension) {
+                    extensions.push(extension);
+                }
+            }
+        }
+
+        formats.push(FormatSpec {
+            description: entry.description,
+            mime: entry.mime,
+            extensions,
+            infer_supported: entry.mime.is_some_and(infer::is_mime_supported),
+        });
+    }
+
+    formats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_png_with_extension() {
+        let formats = supported_formats();
+        assert!(!formats.is_empty());
+
+        let png = formats.iter().find(|format| format.mime == Some("image/png")).unwrap();
+        assert_eq!(png.extensions.first(), Some(&"png"));
+        assert!(png.infer_supported);
+    }
+
+    #[test]
+    fn formats_are_listed_once() {
+        let formats = supported_formats();
+        for (index, format) in formats.iter().enumerate() {
+            assert!(
+                !formats[..index]
+                    .iter()
+                    .any(|earlier| earlier.description == format.description && earlier.mime == format.mime)
+            );
+        }
+    }
+}
@@ -0,0 +1,325 @@
+/// Encoding of textual content, or `Binary` for non-text data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextKind {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Utf32Le,
+    Utf32Be,
+    Ascii,
+    /// Text in a legacy 8-bit encoding such as Latin-1
+    NonUtf8,
+    Binary,
+}
+
+impl TextKind {
+    /// A human-readable description, e.g. "UTF-8 text".
+    pub fn description(&self) -> &'static str {
+        match self {
+            TextKind::Utf8 => "UTF-8 text",
+            TextKind::Utf16Le => "UTF-16 text, little-endian",
+            TextKind::Utf16Be => "UTF-16 text, big-endian",
+            TextKind::Utf32Le => "UTF-32 text, little-endian",
+            TextKind::Utf32Be => "UTF-32 text, big-endian",
+            TextKind::Ascii => "ASCII text",
+            TextKind::NonUtf8 => "Non-UTF-8 text",
+            TextKind::Binary => "Binary data",
+        }
+    }
+}
+
+/// A Unicode byte-order mark at the start of a text file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Bom {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Utf32Le,
+    Utf32Be,
+}
+
+impl Bom {
+    /// Number of bytes the BOM occupies, to skip before parsing the text.
+    pub fn byte_len(&self) -> usize {
+        match self {
+            Bom::Utf8 => 3,
+            Bom::Utf16Le | Bom::Utf16Be => 2,
+            Bom::Utf32Le | Bom::Utf32Be => 4,
+        }
+    }
+
+    /// The encoding the BOM declares.
+    pub fn text_kind(&self) -> TextKind {
+        match self {
+            Bom::Utf8 => TextKind::Utf8,
+            Bom::Utf16Le => TextKind::Utf16Le,
+            Bom::Utf16Be => TextKind::Utf16Be,
+            Bom::Utf32Le => TextKind::Utf32Le,
+            Bom::Utf32Be => TextKind::Utf32Be,
+        }
+    }
+}
+
+/// Detect a byte-order mark at the start of `bytes`. UTF-32 marks are checked
+/// before UTF-16, since the UTF-32 little-endian mark begins with the UTF-16 one.
+pub fn detect_bom(bytes: &[u8]) -> Option<Bom> {
+    const BOMS: &[(&[u8], Bom)] = &[
+        (&[0xFF, 0xFE, 0x00, 0x00], Bom::Utf32Le),
+        (&[0x00, 0x00, 0xFE, 0xFF], Bom::Utf32Be),
+        (&[0xEF, 0xBB, 0xBF], Bom::Utf8),
+        (&[0xFF, 0xFE], Bom::Utf16Le),
+        (&[0xFE, 0xFF], Bom::Utf16Be),
+    ];
+
+    BOMS.iter()
+        .find(|(mark, _)| bytes.starts_with(mark))
+        .map(|(_, bom)| *bom)
+}
+
+/// Number of leading bytes examined by `classify_text`.
+const SAMPLE_LEN: usize = 8 * 1024;
+
+/// Classify a byte slice as text in a particular encoding, or as binary.
+/// A byte-order mark decides the encoding outright; otherwise UTF-16 is
+/// recognized by its pattern of zero bytes, and single-byte text must be
+/// free of NUL and non-whitespace control characters. Such text that isn't
+/// valid UTF-8 is reported as `NonUtf8`.
+/// Only the first 8 KiB are examined. Input that reaches that limit is taken
+/// to be cut from a longer file, so a multi-byte UTF-8 sequence cut off at the
+/// end of it doesn't count as invalid; in shorter input it does.
+/// Returns None for empty input.
+pub fn classify_text(bytes: &[u8]) -> Option<TextKind> {
+    if bytes.is_empty() {
+        return None;
+    }
+    let truncated = bytes.len() >= SAMPLE_LEN;
+    let bytes = &bytes[..bytes.len().min(SAMPLE_LEN)];
+
+    if let Some(bom) = detect_bom(bytes) {
+        return Some(bom.text_kind());
+    }
+
+    if let Some(kind) = guess_utf16(bytes) {
+        return Some(kind);
+    }
+
+    if bytes.iter().any(|&b| is_binary_control(b)) {
+        return Some(TextKind::Binary);
+    }
+
+    if bytes.is_ascii() {
+        return Some(TextKind::Ascii);
+    }
+
+    match std::str::from_utf8(bytes) {
+        Ok(_) => Some(TextKind::Utf8),
+        // Only an incomplete sequence at the very end, which the rest of the
+        // file may complete
+        Err(error) if truncated && error.error_len().is_none() => Some(TextKind::Utf8),
+        Err(_) => Some(TextKind::NonUtf8),
+    }
+}
+
+/// Line terminator convention of a text file, from `line_ending_style`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LineEnding {
+    /// `\n`, as on Unix
+    Lf,
+    /// `\r\n`, as on Windows
+    Crlf,
+    /// `\r` alone, as on classic Mac OS
+    Cr,
+    /// More than one of the above
+    Mixed,
+}
+
+/// Detect the line endings of text, in any encoding `classify_text` recognizes.
+/// Only the first 8 KiB are examined, so a file whose endings change later on
+/// is reported by the style of its beginning.
+/// Returns None for binary data and for text without any line break.
+pub fn line_ending_style(bytes: &[u8]) -> Option<LineEnding> {
+    let kind = classify_text(bytes)?;
+    let truncated = bytes.len() > SAMPLE_LEN;
+    let bytes = &bytes[..bytes.len().min(SAMPLE_LEN)];
+    let bytes = &bytes[detect_bom(bytes).map_or(0, |bom| bom.byte_len())..];
+
+    let width = match kind {
+        TextKind::Binary => return None,
+        TextKind::Utf16Le | TextKind::Utf16Be => 2,
+        TextKind::Utf32Le | TextKind::Utf32Be => 4,
+        TextKind::Utf8 | TextKind::Ascii | TextKind::NonUtf8 => 1,
+    };
+    let little_endian = matches!(kind, TextKind::Utf16Le | TextKind::Utf32Le);
+    let units: Vec<u32> = bytes
+        .chunks_exact(width)
+        .map(|unit| {
+            let unit = unit.iter().map(|&byte| u32::from(byte));
+            if little_endian {
+                unit.rev().fold(0, |value, byte| value << 8 | byte)
+            } else {
+                unit.fold(0, |value, byte| value << 8 | byte)
+            }
+        })
+        .collect();
+
+    let (mut lf, mut crlf, mut cr) = (false, false, false);
+    let mut index = 0;
+    while index < units.len() {
+        match (units[index], units.get(index + 1)) {
+            (0x0D, Some(0x0A)) => {
+                crlf = true;
+                index += 1;
+            }
+            // A trailing CR may be the first half of a CRLF cut off by the sample
+            (0x0D, None) if truncated => {}
+            (0x0D, _) => cr = true,
+            (0x0A, _) => lf = true,
+            _ => {}
+        }
+        index += 1;
+    }
+
+    match (lf, crlf, cr) {
+        (false, false, false) => None,
+        (true, false, false) => Some(LineEnding::Lf),
+        (false, true, false) => Some(LineEnding::Crlf),
+        (false, false, true) => Some(LineEnding::Cr),
+        _ => Some(LineEnding::Mixed),
+    }
+}
+
+/// Control bytes that don't occur in ordinary text. Tab, line feed,
+/// form feed, carriage return and escape are allowed.
+fn is_binary_control(byte: u8) -> bool {
+    matches!(byte, 0x00..=0x08 | 0x0B | 0x0E..=0x1A | 0x1C..=0x1F | 0x7F)
+}
+
+/// Recognize BOM-less UTF-16 holding mostly Latin text, where nearly every
+/// high byte is zero and no low byte is.
+fn guess_utf16(bytes: &[u8]) -> Option<TextKind> {
+    let pairs = bytes.len() / 2;
+    if pairs < 2 {
+        return None;
+    }
+
+    let zeros_at = |parity: usize| {
+        bytes
+            .chunks_exact(2)
+            .filter(|pair| pair[parity] == 0)
+            .count()
+    };
+    let (even_zeros, odd_zeros) = (zeros_at(0), zeros_at(1));
+
+    if odd_zeros * 10 >= pairs * 9 && even_zeros == 0 {
+        Some(TextKind::Utf16Le)
+    } else if even_zeros * 10 >= pairs * 9 && odd_zeros == 0 {
+        Some(TextKind::Utf16Be)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::identify_from_bytes;
+
+    #[test]
+    fn utf8_with_bom() {
+        let bytes = b"\xEF\xBB\xBFcaf\xC3\xA9\n";
+        assert_eq!(classify_text(bytes), Some(TextKind::Utf8));
+        assert_eq!(identify_from_bytes(bytes).unwrap().description, "UTF-8 text");
+    }
+
+    #[test]
+    fn plain_ascii() {
+        let bytes = b"just some words\n";
+        assert_eq!(classify_text(bytes), Some(TextKind::Ascii));
+        assert_eq!(identify_from_bytes(bytes).unwrap().description, "ASCII text");
+    }
+
+    #[test]
+    fn null_bytes_are_binary() {
+        let bytes = b"text\x00with\x00nulls";
+        assert_eq!(classify_text(bytes), Some(TextKind::Binary));
+        assert_eq!(identify_from_bytes(bytes), None);
+    }
+
+    #[test]
+    fn every_bom_variant() {
+        let cases: [(&[u8], Bom, TextKind); 5] = [
+            (b"\xEF\xBB\xBFhi", Bom::Utf8, TextKind::Utf8),
+            (b"\xFF\xFEh\x00i\x00", Bom::Utf16Le, TextKind::Utf16Le),
+            (b"\xFE\xFF\x00h\x00i", Bom::Utf16Be, TextKind::Utf16Be),
+            (b"\xFF\xFE\x00\x00h\x00\x00\x00", Bom::Utf32Le, TextKind::Utf32Le),
+            (b"\x00\x00\xFE\xFF\x00\x00\x00h", Bom::Utf32Be, TextKind::Utf32Be),
+        ];
+        for (bytes, bom, kind) in cases {
+            assert_eq!(detect_bom(bytes), Some(bom));
+            assert_eq!(classify_text(bytes), Some(kind));
+        }
+        assert_eq!(Bom::Utf8.byte_len(), 3);
+        assert_eq!(Bom::Utf32Be.byte_len(), 4);
+    }
+
+    #[test]
+    fn no_bom() {
+        assert_eq!(detect_bom(b"plain"), None);
+        assert_eq!(detect_bom(b""), None);
+        assert_eq!(classify_text(b"plain"), Some(TextKind::Ascii));
+    }
+
+    #[test]
+    fn utf8_versus_other_text() {
+        assert_eq!(classify_text("café\n".as_bytes()), Some(TextKind::Utf8));
+        assert_eq!(classify_text(b"caf\xE9 au lait\n"), Some(TextKind::NonUtf8));
+        assert_eq!(identify_from_bytes(b"caf\xE9 au lait\n").unwrap().description, "Non-UTF-8 text");
+    }
+
+    #[test]
+    fn character_straddling_the_read_boundary_is_valid() {
+        let mut bytes = vec![b'a'; SAMPLE_LEN - 1];
+        bytes.extend_from_slice("é and more".as_bytes());
+        assert_eq!(classify_text(&bytes), Some(TextKind::Utf8));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("straddle.txt");
+        let mut bytes = vec![b'a'; crate::header_read_len() - 1];
+        bytes.extend_from_slice("é".as_bytes());
+        std::fs::write(&path, bytes).unwrap();
+        assert_eq!(crate::identify_multiple(&[&path]).unwrap()[0].description, "UTF-8 text");
+    }
+
+    #[test]
+    fn incomplete_sequence_ending_complete_input_is_invalid() {
+        assert_eq!(classify_text(b"price: 5 \xE2"), Some(TextKind::NonUtf8));
+        assert_eq!(identify_from_bytes(b"price: 5 \xE2").unwrap().description, "Non-UTF-8 text");
+
+        let mut bytes = vec![b'a'; SAMPLE_LEN - 1];
+        bytes.push(0xE2);
+        assert_eq!(classify_text(&bytes), Some(TextKind::Utf8));
+        assert_eq!(classify_text(&bytes[1..]), Some(TextKind::NonUtf8));
+    }
+
+    #[test]
+    fn line_ending_styles() {
+        assert_eq!(line_ending_style(b"one\ntwo\nthree\n"), Some(LineEnding::Lf));
+        assert_eq!(line_ending_style(b"one\r\ntwo\r\nthree\r\n"), Some(LineEnding::Crlf));
+        assert_eq!(line_ending_style(b"one\rtwo\r"), Some(LineEnding::Cr));
+        assert_eq!(line_ending_style(b"one\r\ntwo\nthree\r\n"), Some(LineEnding::Mixed));
+    }
+
+    #[test]
+    fn line_endings_in_utf16() {
+        let crlf: Vec<u8> = "one\r\ntwo\r\n".encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let bytes = [&[0xFF, 0xFE][..], &crlf].concat();
+        assert_eq!(line_ending_style(&bytes), Some(LineEnding::Crlf));
+    }
+
+    #[test]
+    fn no_line_ending_style() {
+        assert_eq!(line_ending_style(b"single line"), None);
+        assert_eq!(line_ending_style(b"\x00\x01\x02\n\x03\x04"), None);
+    }
+}
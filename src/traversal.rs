@@ -0,0 +1,562 @@
+//! Configurable recursive directory traversal.
+
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+
+use walkdir::{DirEntry, WalkDir};
+
+use crate::{FileInfo, FileProcessingError, create_symlink_info, identify_file_from_path, identify_recursive};
+
+/// Options controlling how directories are walked during recursive identification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraversalOptions {
+    /// Maximum depth to descend; 0 is the root itself
+    pub max_depth: usize,
+    /// Follow symbolic links and identify their targets instead of
+    /// reporting them as "Symbolic link"
+    pub follow_links: bool,
+    /// Include hidden entries: names beginning with `.`, and on Windows also
+    /// entries with the hidden attribute. When false, hidden directories are
+    /// not descended into. The root itself is always included.
+    pub include_hidden: bool,
+}
+
+impl Default for TraversalOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: usize::MAX,
+            follow_links: false,
+            include_hidden: true,
+        }
+    }
+}
+
+/// Recursively process a directory using the given traversal options.
+/// Unless `follow_links` is set, symbolic links are reported as "Symbolic link"
+/// rather than identified by their target.
+pub fn identify_recursive_with_options<P: AsRef<Path>>(path: P, options: &TraversalOptions) -> Result<Vec<FileInfo>, FileProcessingError> {
+    let mut results = Vec::new();
+
+    walk(path.as_ref(), options, &|_| true, |file_info| {
+        results.push(file_info);
+        ControlFlow::Continue(())
+    })?;
+
+    Ok(results)
+}
+
+/// Recursively process a directory, grouping results by description as they
+/// are identified. Gives the same groups as `group_by_type(identify_recursive(path)?)`,
+/// each in traversal order, without collecting a flat list first; each
+/// description is copied only once, for its group's key.
+pub fn identify_recursive_grouped<P: AsRef<Path>>(path: P) -> Result<HashMap<String, Vec<FileInfo>>, FileProcessingError> {
+    let mut grouped: HashMap<String, Vec<FileInfo>> = HashMap::new();
+
+    walk(path.as_ref(), &TraversalOptions::default(), &|_| true, |file_info| {
+        match grouped.get_mut(&file_info.description) {
+            Some(group) => group.push(file_info),
+            None => {
+                grouped.insert(file_info.description.clone(), vec![file_info]);
+            }
+        }
+        ControlFlow::Continue(())
+    })?;
+
+    Ok(grouped)
+}
+
+/// Recursively process a directory, calling `on_item` as each entry is identified.
+/// Returning `ControlFlow::Break` from the callback stops the walk immediately, so no
+/// further entries are read. Returns everything identified up to that point,
+/// including the entry that triggered the break.
+pub fn identify_recursive_with_progress<P, F>(path: P, mut on_item: F) -> Result<Vec<FileInfo>, FileProcessingError>
+where
+    P: AsRef<Path>,
+    F: FnMut(&FileInfo) -> ControlFlow<()>,
+{
+    let mut results = Vec::new();
+
+    walk(path.as_ref(), &TraversalOptions::default(), &|_| true, |file_info| {
+        let flow = on_item(&file_info);
+        results.push(file_info);
+        flow
+    })?;
+
+    Ok(results)
+}
+
+/// Recursively process a directory, identifying only files whose path satisfies
+/// `predicate`. Rejected files are skipped before any of their bytes are read.
+/// Directories are always reported and descended into regardless of the predicate.
+pub fn identify_recursive_filtered<P, F>(path: P, predicate: F) -> Result<Vec<FileInfo>, FileProcessingError>
+where
+    P: AsRef<Path>,
+    F: Fn(&Path) -> bool,
+{
+    let mut results = Vec::new();
+
+    walk(path.as_ref(), &TraversalOptions::default(), &predicate, |file_info| {
+        results.push(file_info);
+        ControlFlow::Continue(())
+    })?;
+
+    Ok(results)
+}
+
+/// Recursively process a directory, stopping once `max_files` entries have been
+/// identified. Directories count towards the limit. Which entries are returned
+/// depends on traversal order, which is file system dependent.
+pub fn identify_recursive_limited<P: AsRef<Path>>(path: P, max_files: usize) -> Result<Vec<FileInfo>, FileProcessingError> {
+    let path = path.as_ref();
+    let mut results = Vec::new();
+
+    if max_files == 0 {
+        if !path.exists() {
+            return Err(FileProcessingError::PathNotFound(path.to_path_buf()));
+        }
+        return Ok(results);
+    }
+
+    walk(path, &TraversalOptions::default(), &|_| true, |file_info| {
+        results.push(file_info);
+        if results.len() >= max_files {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    })?;
+
+    Ok(results)
+}
+
+/// Recursively process a directory lazily, identifying each entry only when the
+/// iterator is advanced. Nothing is buffered, so results can be streamed or cut
+/// short with adapters like `take`. A missing path yields a single
+/// `PathNotFound` error.
+pub fn identify_recursive_iter<P: AsRef<Path>>(path: P) -> impl Iterator<Item = Result<FileInfo, FileProcessingError>> {
+    let path = path.as_ref();
+    let options = TraversalOptions::default();
+
+    let missing = (!path.exists()).then(|| Err(FileProcessingError::PathNotFound(path.to_path_buf())));
+    let walker = missing
+        .is_none()
+        .then(|| WalkDir::new(path).max_depth(options.max_depth).follow_links(options.follow_links));
+
+    let entries = walker.into_iter().flatten().map(move |entry| identify_entry(&entry?, &options));
+    missing.into_iter().chain(entries)
+}
+
+/// Recursively process a directory without aborting on errors. Entries that
+/// can't be read, such as unreadable files or directories, are collected with
+/// their paths alongside the results, and the walk continues with their siblings.
+/// A missing `path` is reported as a single `PathNotFound` error.
+pub fn identify_recursive_collect_errors<P: AsRef<Path>>(path: P) -> (Vec<FileInfo>, Vec<(PathBuf, FileProcessingError)>) {
+    let path = path.as_ref();
+    let options = TraversalOptions::default();
+    let mut results = Vec::new();
+    let mut errors = Vec::new();
+
+    if !path.exists() {
+        errors.push((path.to_path_buf(), FileProcessingError::PathNotFound(path.to_path_buf())));
+        return (results, errors);
+    }
+
+    for entry in WalkDir::new(path).max_depth(options.max_depth).follow_links(options.follow_links) {
+        match entry {
+            Ok(entry) => match identify_entry(&entry, &options) {
+                Ok(file_info) => results.push(file_info),
+                Err(error) => errors.push((entry.into_path(), error)),
+            },
+            Err(error) => {
+                let error_path = error.path().unwrap_or(path).to_path_buf();
+                errors.push((error_path, error.into()));
+            }
+        }
+    }
+
+    (results, errors)
+}
+
+/// Recursively process a directory, stopping at the first entry that can't be
+/// read. Returns everything identified before that point, in walk order, with
+/// the error that stopped the walk, or None if the whole tree was processed.
+/// A missing `path` gives no results and a `PathNotFound` error.
+pub fn identify_recursive_until_error<P: AsRef<Path>>(path: P) -> (Vec<FileInfo>, Option<FileProcessingError>) {
+    let mut results = Vec::new();
+
+    for entry in identify_recursive_iter(path) {
+        match entry {
+            Ok(file_info) => results.push(file_info),
+            Err(error) => return (results, Some(error)),
+        }
+    }
+
+    (results, None)
+}
+
+/// Recursively process a directory like `identify_recursive`, but with each
+/// result's path relative to `root`, e.g. `sub/file.png`, so reports don't
+/// depend on where the tree was scanned. The root itself is reported as `.`.
+pub fn identify_recursive_relative<P: AsRef<Path>>(root: P) -> Result<Vec<FileInfo>, FileProcessingError> {
+    let root = root.as_ref();
+
+    let results = identify_recursive(root)?
+        .into_iter()
+        .map(|info| {
+            let path = match info.path.strip_prefix(root) {
+                Ok(relative) if relative.as_os_str().is_empty() => PathBuf::from("."),
+                Ok(relative) => relative.to_path_buf(),
+                Err(_) => info.path.clone(),
+            };
+            FileInfo { path, ..info }
+        })
+        .collect();
+
+    Ok(results)
+}
+
+/// Walk `path` according to `options`, identifying each directory and each other
+/// entry accepted by `include`, and handing it to `visit` until the walk completes
+/// or `visit` breaks.
+fn walk<F>(path: &Path, options: &TraversalOptions, include: &dyn Fn(&Path) -> bool, mut visit: F) -> Result<(), FileProcessingError>
+where
+    F: FnMut(FileInfo) -> ControlFlow<()>,
+{
+    if !path.exists() {
+        return Err(FileProcessingError::PathNotFound(path.to_path_buf()));
+    }
+
+    let walker = WalkDir::new(path)
+        .max_depth(options.max_depth)
+        .follow_links(options.follow_links)
+        .into_iter()
+        .filter_entry(|entry| options.include_hidden || entry.depth() == 0 || !is_hidden(entry));
+
+    for entry in walker {
+        let entry = entry?;
+        let entry_path = entry.path();
+
+        if !entry.file_type().is_dir() && !include(entry_path) {
+            continue;
+        }
+
+        if visit(identify_entry(&entry, options)?).is_break() {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Identify a walked entry, reporting symbolic links as such unless they're followed.
+pub(crate) fn identify_entry(entry: &DirEntry, options: &TraversalOptions) -> Result<FileInfo, FileProcessingError> {
+    if entry.path_is_symlink() && !options.follow_links {
+        return Ok(create_symlink_info(entry.path()));
+    }
+    identify_file_from_path(entry.path())
+}
+
+/// Whether a directory entry is hidden: its name begins with `.`, or on Windows
+/// it has the hidden file attribute.
+pub(crate) fn is_hidden(entry: &DirEntry) -> bool {
+    if entry.file_name().to_string_lossy().starts_with('.') {
+        return true;
+    }
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+
+        const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+        if entry
+            .metadata()
+            .is_ok_and(|metadata| metadata.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0)
+        {
+            return true;
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    #[cfg(unix)]
+    fn link_info(options: &TraversalOptions) -> FileInfo {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("image.png"), b"\x89PNG\r\n\x1a\n").unwrap();
+        std::os::unix::fs::symlink(dir.path().join("image.png"), dir.path().join("link.png")).unwrap();
+
+        identify_recursive_with_options(dir.path(), options)
+            .unwrap()
+            .into_iter()
+            .find(|info| info.path.ends_with("link.png"))
+            .unwrap()
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlink_reported_when_not_following() {
+        let info = link_info(&TraversalOptions::default());
+        assert_eq!(info.description, "Symbolic link");
+        assert_eq!(info.mime_type, None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlink_target_identified_when_following() {
+        let options = TraversalOptions {
+            follow_links: true,
+            ..TraversalOptions::default()
+        };
+        let info = link_info(&options);
+        assert_eq!(info.mime_type.as_deref(), Some("image/png"));
+    }
+
+    /// A directory holding `count` small PNG files.
+    fn dir_with_files(count: usize) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for index in 0..count {
+            fs::write(dir.path().join(format!("file{index}.png")), b"\x89PNG\r\n\x1a\n").unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn progress_callback_sees_every_entry() {
+        let dir = dir_with_files(4);
+        let mut calls = 0;
+        let results = identify_recursive_with_progress(dir.path(), |_| {
+            calls += 1;
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+        assert_eq!(calls, 5);
+        assert_eq!(results.len(), 5);
+    }
+
+    #[test]
+    fn progress_callback_break_stops_the_walk() {
+        let dir = dir_with_files(4);
+        let mut calls = 0;
+        let results = identify_recursive_with_progress(dir.path(), |_| {
+            calls += 1;
+            if calls == 2 { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+        })
+        .unwrap();
+        assert_eq!(calls, 2);
+        assert_eq!(results.len(), 2);
+    }
+
+    fn long_ago() -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(86_400)
+    }
+
+    /// Set a file's access time far in the past, so any later read of it
+    /// moves the access time forward.
+    fn backdate_access(path: &Path) {
+        let times = fs::FileTimes::new().set_accessed(long_ago());
+        fs::File::options().write(true).open(path).unwrap().set_times(times).unwrap();
+    }
+
+    fn was_read(path: &Path) -> bool {
+        fs::metadata(path).unwrap().accessed().unwrap() > long_ago()
+    }
+
+    #[test]
+    fn filter_skips_rejected_files() {
+        let dir = dir_with_files(2);
+        fs::create_dir(dir.path().join("sub")).unwrap();
+        let accepted = dir.path().join("sub/nested.png");
+        let rejected = [dir.path().join("notes.txt"), dir.path().join("sub/data.bin")];
+        fs::write(&accepted, b"\x89PNG\r\n\x1a\n").unwrap();
+        fs::write(&rejected[0], b"text").unwrap();
+        fs::write(&rejected[1], b"\x00\x01").unwrap();
+        for path in rejected.iter().chain([&accepted]) {
+            backdate_access(path);
+        }
+
+        let checked = std::cell::Cell::new(0);
+        let results = identify_recursive_filtered(dir.path(), |path| {
+            checked.set(checked.get() + 1);
+            path.extension().is_some_and(|extension| extension == "png")
+        })
+        .unwrap();
+
+        assert_eq!(checked.get(), 5);
+        let files: Vec<&FileInfo> = results.iter().filter(|info| !info.is_directory).collect();
+        assert_eq!(files.len(), 3);
+        assert!(files.iter().all(|info| info.mime_type.as_deref() == Some("image/png")));
+        assert!(!results.iter().any(|info| rejected.contains(&info.path)));
+        assert_eq!(results.len() - files.len(), 2);
+
+        // Access times aren't updated by every file system or mount option, so
+        // only rely on them if reading the accepted file moved its access time
+        if was_read(&accepted) {
+            assert!(!rejected.iter().any(|path| was_read(path)));
+        }
+    }
+
+    #[test]
+    fn hidden_entries_are_pruned() {
+        let dir = dir_with_files(1);
+        fs::write(dir.path().join(".hidden"), b"secret").unwrap();
+        fs::create_dir(dir.path().join(".git")).unwrap();
+        fs::write(dir.path().join(".git/HEAD"), b"ref: refs/heads/main\n").unwrap();
+
+        let options = TraversalOptions {
+            include_hidden: false,
+            ..TraversalOptions::default()
+        };
+        let names: Vec<String> = identify_recursive_with_options(dir.path(), &options)
+            .unwrap()
+            .iter()
+            .map(|info| info.path.strip_prefix(dir.path()).unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names.len(), 2);
+        assert!(names.iter().all(|name| !name.contains(".git") && !name.contains(".hidden")));
+
+        assert_eq!(identify_recursive(dir.path()).unwrap().len(), 5);
+    }
+
+    #[test]
+    fn limit_stops_after_max_files() {
+        let dir = dir_with_files(10);
+        assert_eq!(identify_recursive_limited(dir.path(), 3).unwrap().len(), 3);
+        assert_eq!(identify_recursive_limited(dir.path(), 100).unwrap().len(), 11);
+        assert!(identify_recursive_limited(dir.path(), 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn iterator_identifies_lazily() {
+        let dir = dir_with_files(3);
+        let mut results = identify_recursive_iter(dir.path());
+
+        let taken: Vec<FileInfo> = results.by_ref().take(2).collect::<Result<_, _>>().unwrap();
+        assert_eq!(taken.len(), 2);
+        assert_eq!(taken[0].path, dir.path());
+
+        // Entries not yet reached haven't been read, so removing them now is
+        // noticed when the iterator gets to them
+        for index in 0..3 {
+            let path = dir.path().join(format!("file{index}.png"));
+            if path != taken[1].path {
+                fs::remove_file(path).unwrap();
+            }
+        }
+        assert!(results.all(|result| result.is_err()));
+    }
+
+    #[test]
+    fn unreadable_directory_does_not_hide_siblings() {
+        let dir = dir_with_files(1);
+        let locked = dir.path().join("locked");
+        fs::create_dir(&locked).unwrap();
+        fs::write(locked.join("inner.png"), b"\x89PNG\r\n\x1a\n").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+        }
+        let enforced = fs::read_dir(&locked).is_err();
+
+        let (results, errors) = identify_recursive_collect_errors(dir.path());
+        assert!(results.iter().any(|info| info.path == dir.path().join("file0.png")));
+        // Permission bits aren't enforced for privileged users
+        if enforced {
+            assert_eq!(errors.len(), 1);
+            assert_eq!(errors[0].0, locked);
+        } else {
+            assert!(errors.is_empty());
+            assert_eq!(results.len(), 4);
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+        }
+    }
+
+    #[test]
+    fn missing_root_is_a_single_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let (results, errors) = identify_recursive_collect_errors(dir.path().join("missing"));
+        assert!(results.is_empty());
+        assert!(matches!(errors[..], [(_, FileProcessingError::PathNotFound(_))]));
+    }
+
+    #[test]
+    fn until_error_keeps_results_before_the_error() {
+        let dir = dir_with_files(2);
+        let locked = dir.path().join("locked");
+        fs::create_dir(&locked).unwrap();
+        fs::write(locked.join("inner.png"), b"\x89PNG\r\n\x1a\n").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+        }
+        let enforced = fs::read_dir(&locked).is_err();
+
+        let (results, error) = identify_recursive_until_error(dir.path());
+        // Permission bits aren't enforced for privileged users
+        if enforced {
+            assert!(error.is_some());
+            assert_eq!(results[0].path, dir.path());
+            assert!(results.iter().any(|info| info.path == locked));
+            assert!(!results.iter().any(|info| info.path == locked.join("inner.png")));
+        } else {
+            assert!(error.is_none());
+            assert_eq!(results.len(), 5);
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+        }
+    }
+
+    #[test]
+    fn until_error_reports_missing_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let (results, error) = identify_recursive_until_error(dir.path().join("missing"));
+        assert!(results.is_empty());
+        assert!(matches!(error, Some(FileProcessingError::PathNotFound(_))));
+    }
+
+    #[test]
+    fn relative_paths_from_root() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("sub")).unwrap();
+        fs::write(dir.path().join("sub/file.png"), b"\x89PNG\r\n\x1a\n").unwrap();
+        fs::write(dir.path().join("top.txt"), b"hello\n").unwrap();
+
+        let results = identify_recursive_relative(dir.path()).unwrap();
+        let mut paths: Vec<&Path> = results.iter().map(|info| info.path.as_path()).collect();
+        paths.sort();
+        assert_eq!(paths, [Path::new("."), Path::new("sub"), Path::new("sub/file.png"), Path::new("top.txt")]);
+
+        let png = results.iter().find(|info| info.path == Path::new("sub/file.png")).unwrap();
+        assert_eq!(png.mime_type.as_deref(), Some("image/png"));
+    }
+
+    #[test]
+    fn grouped_matches_two_step_grouping() {
+        let dir = dir_with_files(3);
+        fs::create_dir(dir.path().join("sub")).unwrap();
+        fs::write(dir.path().join("sub/notes.txt"), b"hello\n").unwrap();
+        fs::write(dir.path().join("sub/empty"), b"").unwrap();
+
+        let grouped = identify_recursive_grouped(dir.path()).unwrap();
+        assert_eq!(grouped, crate::group_by_type(identify_recursive(dir.path()).unwrap()));
+        assert_eq!(grouped.values().map(Vec::len).sum::<usize>(), 7);
+    }
+}
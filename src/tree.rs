@@ -0,0 +1,101 @@
+//! Recursive identification that keeps the directory hierarchy.
+
+use std::path::{Path, PathBuf};
+
+use crate::{FileInfo, FileProcessingError, identify_dir_children};
+
+/// A directory and everything below it, from `identify_tree`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DirNode {
+    /// The path to the directory, serialized as a (lossy UTF-8) string
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::report::serialize_path_lossy"))]
+    pub path: PathBuf,
+    /// The directory's entries, sorted by path
+    pub children: Vec<TreeEntry>,
+}
+
+/// An entry in a `DirNode`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TreeEntry {
+    /// Anything other than a directory, including symbolic links
+    File(FileInfo),
+    /// A subdirectory with its own entries
+    Directory(DirNode),
+}
+
+/// Recursively process a directory, nesting each entry under its parent
+/// directory instead of returning a flat list like `identify_recursive`.
+/// Symbolic links are reported as "Symbolic link" and not followed, so the
+/// tree is always finite. Returns `NotADirectory` if `path` isn't a directory.
+pub fn identify_tree<P: AsRef<Path>>(path: P) -> Result<DirNode, FileProcessingError> {
+    let path = path.as_ref();
+
+    let children = identify_dir_children(path)?
+        .into_iter()
+        .map(|info| {
+            if info.is_directory {
+                identify_tree(&info.path).map(TreeEntry::Directory)
+            } else {
+                Ok(TreeEntry::File(info))
+            }
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(DirNode {
+        path: path.to_path_buf(),
+        children,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    /// Each entry as its name, followed by its MIME type for files or its own
+    /// entries in brackets for directories.
+    fn shape(node: &DirNode) -> Vec<String> {
+        node.children
+            .iter()
+            .map(|entry| match entry {
+                TreeEntry::File(info) => format!("{} {:?}", name(&info.path), info.mime_type.as_deref()),
+                TreeEntry::Directory(child) => format!("{} [{}]", name(&child.path), shape(child).join(", ")),
+            })
+            .collect()
+    }
+
+    fn name(path: &Path) -> String {
+        path.file_name().unwrap().to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn two_level_tree_is_nested() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("sub/deeper")).unwrap();
+        fs::write(root.join("top.png"), b"\x89PNG\r\n\x1a\n").unwrap();
+        fs::write(root.join("sub/notes.txt"), b"hello\n").unwrap();
+        fs::write(root.join("sub/deeper/image.png"), b"\x89PNG\r\n\x1a\n").unwrap();
+
+        let tree = identify_tree(root).unwrap();
+        assert_eq!(tree.path, root);
+        assert_eq!(
+            shape(&tree),
+            [
+                r#"sub [deeper [image.png Some("image/png")], notes.txt Some("text/plain")]"#,
+                r#"top.png Some("image/png")"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn file_root_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file.txt");
+        fs::write(&path, b"hello\n").unwrap();
+        assert!(matches!(identify_tree(&path), Err(FileProcessingError::NotADirectory(_))));
+    }
+}
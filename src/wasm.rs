@@ -0,0 +1,36 @@
+//! JavaScript bindings for WebAssembly frontends, enabled by the `wasm` feature.
+//! There is no file system in the browser, so only the byte-based API is exported.
+
+use wasm_bindgen::prelude::*;
+
+use crate::identify_from_bytes;
+
+/// Identify the file type of a byte buffer, such as the contents of a `File`
+/// read into a `Uint8Array`. Returns the FileInfo as a plain JavaScript object
+/// (with an empty `path`), or `null` if the type is not recognized.
+#[wasm_bindgen]
+pub fn identify_bytes(data: &[u8]) -> JsValue {
+    match identify_from_bytes(data) {
+        Some(info) => serde_wasm_bindgen::to_value(&info).expect("FileInfo serialization is infallible"),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[wasm_bindgen_test]
+    fn png_buffer_is_identified() {
+        let value = identify_bytes(b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR");
+        let info: crate::FileInfo = serde_wasm_bindgen::from_value(value).unwrap();
+        assert_eq!(info.mime_type.as_deref(), Some("image/png"));
+    }
+
+    #[wasm_bindgen_test]
+    fn unrecognized_buffer_is_null() {
+        assert!(identify_bytes(&[0x00, 0xDE, 0xAD, 0x00]).is_null());
+    }
+}
@@ -0,0 +1,105 @@
+//! Recognition of XML-based formats by their root element.
+
+use crate::containers::find;
+
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+
+const XHTML_NAMESPACE: &[u8] = b"http://www.w3.org/1999/xhtml";
+const ATOM_NAMESPACE: &[u8] = b"http://www.w3.org/2005/Atom";
+const RSS1_NAMESPACE: &[u8] = b"http://purl.org/rss/1.0/";
+
+/// Identify the XML dialect of a document, such as SVG, XHTML or an RSS or
+/// Atom feed, from its root element and namespaces. Leading whitespace, a
+/// UTF-8 byte-order mark, the XML declaration, comments, processing
+/// instructions and a DOCTYPE may precede the root element.
+/// Returns "XML document" for other documents with an XML declaration, and
+/// None for input that isn't XML, including HTML that isn't XHTML.
+pub fn xml_dialect(bytes: &[u8]) -> Option<String> {
+    xml_kind(bytes).map(|(description, _)| description.to_string())
+}
+
+/// Description and MIME type of an XML document's dialect.
+pub(crate) fn xml_kind(bytes: &[u8]) -> Option<(&'static str, &'static str)> {
+    let bytes = bytes.strip_prefix(UTF8_BOM).unwrap_or(bytes).trim_ascii_start();
+    let has_declaration = bytes.starts_with(b"<?xml");
+    let root = root_element(bytes)?;
+
+    // The root's name without any namespace prefix, e.g. "RDF" for "rdf:RDF"
+    let name_end = root
+        .iter()
+        .position(|&byte| byte.is_ascii_whitespace() || byte == b'/' || byte == b'>')
+        .unwrap_or(root.len());
+    let name = &root[..name_end];
+    let local_name = name.rsplit(|&byte| byte == b':').next().unwrap_or(name);
+    let declares = |namespace: &[u8]| contains(root, namespace);
+
+    Some(match local_name {
+        b"svg" => ("Scalable Vector Graphics (SVG)", "image/svg+xml"),
+        b"html" if has_declaration || declares(XHTML_NAMESPACE) => ("XHTML document", "application/xhtml+xml"),
+        b"rss" => ("RSS feed", "application/rss+xml"),
+        b"RDF" if declares(RSS1_NAMESPACE) => ("RSS feed", "application/rss+xml"),
+        b"feed" if declares(ATOM_NAMESPACE) => ("Atom feed", "application/atom+xml"),
+        _ if has_declaration => ("XML document", "text/xml"),
+        _ => return None,
+    })
+}
+
+/// The start tag of the root element, from after its `<` up to its `>` or the
+/// end of the input, skipping the prolog before it.
+fn root_element(mut bytes: &[u8]) -> Option<&[u8]> {
+    loop {
+        bytes = bytes.trim_ascii_start();
+        let skip_to = if bytes.starts_with(b"<?") {
+            b"?>" as &[u8]
+        } else if bytes.starts_with(b"<!--") {
+            b"-->"
+        } else if bytes.starts_with(b"<!") {
+            // A DOCTYPE's internal subset may itself contain `>`
+            match (bytes.iter().position(|&byte| byte == b'['), bytes.iter().position(|&byte| byte == b'>')) {
+                (Some(open), Some(close)) if open < close => b"]>" as &[u8],
+                _ => b">",
+            }
+        } else if let Some(tag) = bytes.strip_prefix(b"<") {
+            let end = tag.iter().position(|&byte| byte == b'>').unwrap_or(tag.len());
+            return tag.first().is_some_and(u8::is_ascii_alphabetic).then_some(&tag[..end]);
+        } else {
+            return None;
+        };
+
+        let end = find(bytes, skip_to)?;
+        bytes = &bytes[end + skip_to.len()..];
+    }
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    find(haystack, needle).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn svg_document() {
+        let svg = b"\xef\xbb\xbf\n  <?xml version=\"1.0\"?>\n<!-- drawn by hand -->\n<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"10\"/>";
+        assert_eq!(xml_dialect(svg).as_deref(), Some("Scalable Vector Graphics (SVG)"));
+        assert_eq!(crate::identify_from_bytes(svg).unwrap().mime_type.as_deref(), Some("image/svg+xml"));
+
+        let bare = b"<svg xmlns=\"http://www.w3.org/2000/svg\"></svg>";
+        assert_eq!(crate::identify_from_bytes(bare).unwrap().mime_type.as_deref(), Some("image/svg+xml"));
+    }
+
+    #[test]
+    fn atom_feed() {
+        let atom = b"<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<feed xmlns=\"http://www.w3.org/2005/Atom\">\n<title>News</title></feed>";
+        assert_eq!(xml_dialect(atom).as_deref(), Some("Atom feed"));
+        assert_eq!(crate::identify_from_bytes(atom).unwrap().mime_type.as_deref(), Some("application/atom+xml"));
+    }
+
+    #[test]
+    fn plain_xml_and_non_xml() {
+        assert_eq!(xml_dialect(b"<?xml version=\"1.0\"?><config/>").as_deref(), Some("XML document"));
+        assert_eq!(xml_dialect(b"<feed><title/></feed>"), None);
+        assert_eq!(xml_dialect(b"plain text"), None);
+    }
+}